@@ -1,7 +1,8 @@
 use crate::{
-    AHashMap, AHashSet, GpuRenderer, GraphicsError, TextureGroup,
-    TextureLayout, UVec3,
+    AHashMap, AHashSet, AsTextureGroup, GpuRenderer, GraphicsError,
+    SamplerSettings, TextureGroup, TextureLayout, UVec3,
 };
+use log::error;
 use lru::LruCache;
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
@@ -12,12 +13,21 @@ use wgpu::{BindGroup, BindGroupLayout, TextureUsages};
 mod allocation;
 mod allocator;
 mod atlas;
+mod export;
 mod migration;
+mod mipmap;
+mod packer;
+mod shrink;
+mod stats;
 
-pub use allocation::Allocation;
+pub use allocation::{Allocation, pad_pixels};
 pub use allocator::Allocator;
 pub use atlas::Atlas;
+pub use export::*;
 use migration::*;
+use mipmap::MipmapGenerator;
+pub use packer::*;
+pub use stats::*;
 /**
  * AtlasSet is used to hold and contain the data of many Atlas layers.
  * Each Atlas keeps track of the allocations allowed. Each allocation is a
@@ -41,6 +51,12 @@ use migration::*;
  * knowing when to migrate the atlas by setting a deallocations_limit. We also can know when to unload a
  * empty layer by using the layer_free_limit. This will allow us to control VRam usage.
  *
+ * *******************************COMPRESSION**********************************************
+ * Block compressed formats (BCn, ETC2, EAC and ASTC) store blocks of pixels rather than
+ * single pixels. Allocations in such a AtlasSet get rounded up to whole blocks so every
+ * allocation starts and ends on a block, meaning the Allocation's rect covers whole blocks.
+ * Padding is not applied as it can not be extruded without decoding. See [`AtlasSet::try_new`] and [`AtlasSet::supports_format`].
+ *
  * TODO Keep track of Indexs within an Atlas.
 */
 pub struct AtlasSet<U: Hash + Eq + Clone = String, Data: Copy + Default = i32> {
@@ -75,7 +91,7 @@ pub struct AtlasSet<U: Hash + Eq + Clone = String, Data: Copy + Default = i32> {
     /// amount of layers in memory before we start checking for fragmentations.
     pub layer_check_limit: usize,
     /// When we should free empty layers. this must be more than 1 otherwise will cause
-    /// issues.
+    /// issues. See [`AtlasSet::should_shrink`] and [`AtlasSet::shrink`].
     pub layer_free_limit: usize,
     /// uses the refcount to unload rather than the unused.
     /// must exist for fonts to unload correctly and must be set to false for them.
@@ -84,6 +100,16 @@ pub struct AtlasSet<U: Hash + Eq + Clone = String, Data: Copy + Default = i32> {
     pub texture_group: TextureGroup,
     /// Used to Migrate Textures to reduce Fragmentation.
     pub migration: Option<MigrationTask>,
+    /// Filtering and Addressing the Texture gets sampled with.
+    sampler: SamplerSettings,
+    /// Amount of mip levels the Texture has. 1 when not mipmapped.
+    mip_level_count: u32,
+    /// Pixels reserved around each new Allocation.
+    padding: u32,
+    /// If the padding repeats the edge pixels instead of staying transparent.
+    extrude: bool,
+    /// Renders the mip levels when mipmapped.
+    mipmaps: Option<MipmapGenerator>,
 }
 
 impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
@@ -93,6 +119,17 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         height: u32,
         data: Data,
     ) -> Option<Allocation<Data>> {
+        let (block_width, block_height) = self.format.block_dimensions();
+        let padding = if self.format.is_compressed() {
+            0
+        } else {
+            self.padding
+        };
+        let (width, height) = (
+            (width + padding * 2).next_multiple_of(block_width),
+            (height + padding * 2).next_multiple_of(block_height),
+        );
+
         /* Check if the allocation would fit. */
         if width > self.size || height > self.size {
             return None;
@@ -109,6 +146,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
                     allocation,
                     layer: i,
                     data,
+                    padding,
                 });
             }
         }
@@ -135,6 +173,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
                             allocation,
                             layer: layer_id,
                             data,
+                            padding,
                         });
                     }
                 }
@@ -161,6 +200,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
                 allocation,
                 layer: self.layers.len() - 1,
                 data,
+                padding,
             });
         }
 
@@ -168,31 +208,12 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         None
     }
 
-    //TODO Add shrink that takes layers using a unload boolean and also promote each layers.
-    //TODO allocation layers to the new layer location. while removing the old empty layer.
     fn grow(&mut self, amount: usize, renderer: &GpuRenderer) {
         if amount == 0 {
             return;
         }
 
-        let texture =
-            renderer.device().create_texture(&wgpu::TextureDescriptor {
-                label: Some("Texture"),
-                size: wgpu::Extent3d {
-                    width: self.size,
-                    height: self.size,
-                    depth_or_array_layers: self.layers.len() as u32,
-                },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: self.format,
-                usage: wgpu::TextureUsages::TEXTURE_BINDING
-                    | wgpu::TextureUsages::COPY_DST
-                    | wgpu::TextureUsages::COPY_SRC,
-                view_formats: &[self.format],
-            });
-
+        let texture = self.create_texture(renderer, self.layers.len() as u32);
         let amount_to_copy = self.layers.len() - amount;
 
         let mut encoder = renderer.device().create_command_encoder(
@@ -201,35 +222,114 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
             },
         );
 
-        for (i, _) in self.layers.iter_mut().take(amount_to_copy).enumerate() {
-            let origin = wgpu::Origin3d {
-                x: 0,
-                y: 0,
-                z: i as u32,
-            };
+        for i in 0..amount_to_copy {
+            self.copy_layer(&mut encoder, &texture, i as u32, i as u32);
+        }
+
+        self.texture = texture;
+        self.update_texture_group(renderer);
+        renderer.queue().submit(std::iter::once(encoder.finish()));
+    }
+
+    /// Creates a Texture able to hold the amount of layers using the
+    /// [`AtlasSet`]s size, format and mip level count.
+    ///
+    fn create_texture(
+        &self,
+        renderer: &GpuRenderer,
+        layers: u32,
+    ) -> wgpu::Texture {
+        let mut usage = wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_DST
+            | wgpu::TextureUsages::COPY_SRC;
+
+        if self.mip_level_count > 1 {
+            usage |= wgpu::TextureUsages::RENDER_ATTACHMENT;
+        }
+
+        renderer.device().create_texture(&wgpu::TextureDescriptor {
+            label: Some("Texture"),
+            size: wgpu::Extent3d {
+                width: self.size,
+                height: self.size,
+                depth_or_array_layers: layers,
+            },
+            mip_level_count: self.mip_level_count,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.format,
+            usage,
+            view_formats: &[self.format],
+        })
+    }
+
+    /// Copies every mip level of a layer from the current Texture into the
+    /// layer of another Texture.
+    ///
+    fn copy_layer(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        texture: &wgpu::Texture,
+        from: u32,
+        to: u32,
+    ) {
+        let levels = self
+            .texture
+            .mip_level_count()
+            .min(texture.mip_level_count());
 
+        for level in 0..levels {
             encoder.copy_texture_to_texture(
                 wgpu::TexelCopyTextureInfo {
                     texture: &self.texture,
-                    mip_level: 0,
-                    origin,
+                    mip_level: level,
+                    origin: wgpu::Origin3d {
+                        x: 0,
+                        y: 0,
+                        z: from,
+                    },
                     aspect: wgpu::TextureAspect::All,
                 },
                 wgpu::TexelCopyTextureInfo {
-                    texture: &texture,
-                    mip_level: 0,
-                    origin,
+                    texture,
+                    mip_level: level,
+                    origin: wgpu::Origin3d { x: 0, y: 0, z: to },
                     aspect: wgpu::TextureAspect::All,
                 },
                 wgpu::Extent3d {
-                    width: self.size,
-                    height: self.size,
+                    width: (self.size >> level).max(1),
+                    height: (self.size >> level).max(1),
                     depth_or_array_layers: 1,
                 },
             );
         }
+    }
 
-        self.texture = texture;
+    /// Regenerates the mip levels covering the rectangle of a layer.
+    /// Does nothing when the [`AtlasSet`] is not mipmapped.
+    ///
+    pub(crate) fn generate_mipmaps(
+        &self,
+        renderer: &GpuRenderer,
+        encoder: &mut wgpu::CommandEncoder,
+        layer: usize,
+        rect: (u32, u32, u32, u32),
+    ) {
+        if let Some(mipmaps) = &self.mipmaps {
+            mipmaps.generate(
+                renderer,
+                encoder,
+                &self.texture,
+                layer as u32,
+                rect,
+            );
+        }
+    }
+
+    /// Recreates the [`TextureGroup`] from the current Texture, Layers and
+    /// [`SamplerSettings`].
+    ///
+    fn update_texture_group(&mut self, renderer: &GpuRenderer) {
         let texture_view =
             self.texture.create_view(&wgpu::TextureViewDescriptor {
                 label: Some("Texture Atlas"),
@@ -237,7 +337,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
                 dimension: Some(wgpu::TextureViewDimension::D2Array),
                 aspect: wgpu::TextureAspect::All,
                 base_mip_level: 0,
-                mip_level_count: Some(1),
+                mip_level_count: Some(self.mip_level_count),
                 base_array_layer: 0,
                 array_layer_count: Some(self.layers.len() as u32),
                 usage: Some(
@@ -249,9 +349,61 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         let atlas_layout: Arc<BindGroupLayout> = renderer
             .get_layout(TextureLayout)
             .expect("TextureLayout was never created.");
-        self.texture_group =
-            TextureGroup::from_view(renderer, texture_view, &atlas_layout);
-        renderer.queue().submit(std::iter::once(encoder.finish()));
+        let sampler = SamplerSettings {
+            max_mip_level: self.mip_level_count - 1,
+            ..self.sampler
+        };
+
+        self.texture_group = TextureGroup::from_view_with_sampler(
+            renderer,
+            texture_view,
+            &atlas_layout,
+            &sampler,
+        );
+    }
+
+    /// If the device can create and sample an [`AtlasSet`] of format, going
+    /// by [`GpuRenderer::texture_format_features`].
+    /// The format must be filterable and copyable. Block compressed formats
+    /// need their [`wgpu::Features`] to be requested when creating the
+    /// device, for example [`wgpu::Features::TEXTURE_COMPRESSION_BC`].
+    ///
+    pub fn supports_format(
+        renderer: &GpuRenderer,
+        format: wgpu::TextureFormat,
+    ) -> bool {
+        let features = renderer.texture_format_features(format);
+
+        format.block_copy_size(None).is_some()
+            && renderer
+                .device()
+                .features()
+                .contains(format.required_features())
+            && features
+                .flags
+                .contains(wgpu::TextureFormatFeatureFlags::FILTERABLE)
+            && features.allowed_usages.contains(
+                TextureUsages::TEXTURE_BINDING
+                    | TextureUsages::COPY_DST
+                    | TextureUsages::COPY_SRC,
+            )
+    }
+
+    /// Creates a new [`AtlasSet`] after checking the device supports the
+    /// format. Use this over [`AtlasSet::new`] for block compressed formats.
+    /// See [`AtlasSet::supports_format`].
+    ///
+    pub fn try_new(
+        renderer: &mut GpuRenderer,
+        format: wgpu::TextureFormat,
+        use_ref_count: bool,
+        size: u32,
+    ) -> Result<Self, GraphicsError> {
+        if !Self::supports_format(renderer, format) {
+            return Err(GraphicsError::UnsupportedFormat(format));
+        }
+
+        Ok(Self::new(renderer, format, use_ref_count, size))
     }
 
     /// Creates a new [`AtlasSet`].
@@ -260,6 +412,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
     /// - format: [`wgpu::TextureFormat`] the texture layers will need to be.
     /// - use_ref_count: Mostly used for Glyph Storage and Auto Removal.
     /// - size: Used for both Width and Height. Limited to max of limits.max_texture_dimension_2d and min of 256.
+    ///   Rounded down to whole blocks for block compressed formats.
     ///
     pub fn new(
         renderer: &mut GpuRenderer,
@@ -269,6 +422,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
     ) -> Self {
         let limits = renderer.device().limits();
         let size = size.clamp(256, limits.max_texture_dimension_2d);
+        let size = size - size % block_alignment(format);
 
         let extent = wgpu::Extent3d {
             width: size,
@@ -336,20 +490,96 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
             use_ref_count,
             texture_group,
             migration: None,
+            sampler: SamplerSettings::default(),
+            mip_level_count: 1,
+            padding: 0,
+            extrude: true,
+            mipmaps: None,
         }
     }
 
+    /// Returns the bytes each block of the Texture uses. A block is a single
+    /// pixel for uncompressed formats.
+    ///
+    fn block_size(&self) -> Result<u32, GraphicsError> {
+        self.format
+            .block_copy_size(None)
+            .ok_or(GraphicsError::UnsupportedFormat(self.format))
+    }
+
+    /// Returns the bytes a Texture of Width and Height must hold to be
+    /// uploaded, derived from the format's block size. Rows are tightly
+    /// packed and block compressed formats count whole blocks.
+    ///
+    pub fn upload_byte_size(
+        &self,
+        width: u32,
+        height: u32,
+    ) -> Result<usize, GraphicsError> {
+        let (block_width, block_height) = self.format.block_dimensions();
+
+        Ok(width.div_ceil(block_width) as usize
+            * height.div_ceil(block_height) as usize
+            * self.block_size()? as usize)
+    }
+
+    /// Checks the buffer holds exactly the bytes a Texture of Width and
+    /// Height needs. See [`AtlasSet::upload_byte_size`].
+    ///
+    fn validate_upload(
+        &self,
+        buffer: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<(), GraphicsError> {
+        let needed = self.upload_byte_size(width, height)?;
+
+        if buffer.len() != needed {
+            return Err(GraphicsError::BufferSizeMismatch(
+                needed,
+                buffer.len(),
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Uploads a new Texture Byte Array into the GPU AtlasSets Layer.
+    /// The padding around the Allocation gets extruded or cleared and the
+    /// mip levels are regenerated when mipmapped. Block compressed bytes
+    /// must cover the whole Allocation in rows of blocks.
+    ///
+    /// Returns a [`GraphicsError`] if the buffer does not hold exactly the
+    /// bytes of the Allocation's size in the [`AtlasSet`]'s format.
     ///
     pub fn upload_allocation(
         &mut self,
         buffer: &[u8],
         allocation: &Allocation<Data>,
         renderer: &GpuRenderer,
-    ) {
-        let (x, y) = allocation.position();
-        let (width, height) = allocation.size();
+    ) -> Result<(), GraphicsError> {
+        let (x, y, width, height) = allocation.outer_rect();
+        let (inner_width, inner_height) = allocation.size();
         let layer = allocation.layer;
+        let block_size = self.block_size()?;
+        let (block_width, block_height) = self.format.block_dimensions();
+        let padded;
+
+        self.validate_upload(buffer, inner_width, inner_height)?;
+
+        let buffer = if allocation.padding > 0 {
+            padded = pad_pixels(
+                buffer,
+                inner_width,
+                inner_height,
+                block_size,
+                allocation.padding,
+                self.extrude,
+            );
+            padded.as_slice()
+        } else {
+            buffer
+        };
 
         renderer.queue().write_texture(
             wgpu::TexelCopyTextureInfo {
@@ -365,14 +595,8 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
             buffer,
             wgpu::TexelCopyBufferLayout {
                 offset: 0,
-                bytes_per_row: Some(
-                    if self.format == wgpu::TextureFormat::Rgba8UnormSrgb {
-                        4 * width
-                    } else {
-                        width
-                    },
-                ),
-                rows_per_image: Some(height),
+                bytes_per_row: Some(width.div_ceil(block_width) * block_size),
+                rows_per_image: Some(height.div_ceil(block_height)),
             },
             wgpu::Extent3d {
                 width,
@@ -380,6 +604,24 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
                 depth_or_array_layers: 1,
             },
         );
+
+        if self.mipmaps.is_some() {
+            let mut encoder = renderer.device().create_command_encoder(
+                &wgpu::CommandEncoderDescriptor {
+                    label: Some("Mipmap command encoder"),
+                },
+            );
+
+            self.generate_mipmaps(
+                renderer,
+                &mut encoder,
+                layer,
+                allocation.outer_rect(),
+            );
+            renderer.queue().submit(std::iter::once(encoder.finish()));
+        }
+
+        Ok(())
     }
 
     /// Clears all information of stored Textures and Allocations.
@@ -463,8 +705,16 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
                 }
 
                 self.layers[allocation.layer].insert_index(id);
+                self.generate_mipmaps(
+                    renderer,
+                    &mut encoder,
+                    allocation.layer,
+                    allocation.outer_rect(),
+                );
             }
 
+            renderer.queue().submit(std::iter::once(encoder.finish()));
+
             if !task.migrating.is_empty() {
                 self.migration = Some(task);
             }
@@ -631,7 +881,39 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         Some(allocation.layer)
     }
 
+    /// Validates the bytes, allocates room for them and uploads them.
+    /// The Allocation is given back to its layer if the upload fails.
+    /// Returns None if there is no room.
+    ///
+    fn place(
+        &mut self,
+        bytes: &[u8],
+        width: u32,
+        height: u32,
+        data: Data,
+        renderer: &GpuRenderer,
+    ) -> Result<Option<Allocation<Data>>, GraphicsError> {
+        self.validate_upload(bytes, width, height)?;
+
+        let nlayers = self.layers.len();
+        let Some(allocation) = self.allocate(width, height, data) else {
+            return Ok(None);
+        };
+        self.grow(self.layers.len() - nlayers, renderer);
+
+        if let Err(e) = self.upload_allocation(bytes, &allocation, renderer) {
+            self.layers[allocation.layer]
+                .allocator
+                .cancel(allocation.allocation);
+            return Err(e);
+        }
+
+        Ok(Some(allocation))
+    }
+
     /// Uploads Texture byte array to the AtlasSet returning the created [`Allocation`]s Index.
+    /// Returns None if there is no room. Errors are logged, use
+    /// [`AtlasSet::try_upload`] to handle them.
     ///
     /// # Arguments
     /// - bytes: Textures Byte array.
@@ -649,27 +931,37 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         data: Data,
         renderer: &GpuRenderer,
     ) -> Option<usize> {
-        if let Some(&id) = self.lookup.get(&key) {
-            Some(id)
-        } else {
-            let allocation = {
-                let nlayers = self.layers.len();
-                let allocation = self.allocate(width, height, data)?;
-                self.grow(self.layers.len() - nlayers, renderer);
-
-                allocation
-            };
-
-            self.upload_allocation(bytes, &allocation, renderer);
-            let id = self.store.insert((allocation, key.clone()));
-            self.layers[allocation.layer].insert_index(id);
-            self.lookup.insert(key, id);
-            self.cache.push(id, 1);
-            Some(id)
-        }
+        self.try_upload(key, bytes, width, height, data, renderer)
+            .unwrap_or_else(|e| {
+                error!("Texture could not be uploaded: {e}");
+                None
+            })
+    }
+
+    /// Uploads Texture byte array to the AtlasSet returning the created [`Allocation`]s Index.
+    /// Returns None if there is no room.
+    ///
+    /// Returns a [`GraphicsError`] if the bytes do not match
+    /// [`AtlasSet::upload_byte_size`].
+    ///
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_upload(
+        &mut self,
+        key: U,
+        bytes: &[u8],
+        width: u32,
+        height: u32,
+        data: Data,
+        renderer: &GpuRenderer,
+    ) -> Result<Option<usize>, GraphicsError> {
+        Ok(self
+            .try_upload_with_alloc(key, bytes, width, height, data, renderer)?
+            .map(|(id, _)| id))
     }
 
     /// Uploads Texture byte array to the AtlasSet returning the created [`Allocation`] and Index.
+    /// Returns None if there is no room. Errors are logged, use
+    /// [`AtlasSet::try_upload_with_alloc`] to handle them.
     ///
     /// # Arguments
     /// - bytes: Textures Byte array.
@@ -687,25 +979,107 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         data: Data,
         renderer: &GpuRenderer,
     ) -> Option<(usize, Allocation<Data>)> {
+        self.try_upload_with_alloc(key, bytes, width, height, data, renderer)
+            .unwrap_or_else(|e| {
+                error!("Texture could not be uploaded: {e}");
+                None
+            })
+    }
+
+    /// Uploads Texture byte array to the AtlasSet returning the created [`Allocation`] and Index.
+    /// Returns None if there is no room.
+    ///
+    /// Returns a [`GraphicsError`] if the bytes do not match
+    /// [`AtlasSet::upload_byte_size`].
+    ///
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_upload_with_alloc(
+        &mut self,
+        key: U,
+        bytes: &[u8],
+        width: u32,
+        height: u32,
+        data: Data,
+        renderer: &GpuRenderer,
+    ) -> Result<Option<(usize, Allocation<Data>)>, GraphicsError> {
         if let Some(&id) = self.lookup.get(&key) {
-            let (allocation, _) = self.store.get(id)?;
-            Some((id, *allocation))
-        } else {
-            let allocation = {
-                let nlayers = self.layers.len();
-                let allocation = self.allocate(width, height, data)?;
-                self.grow(self.layers.len() - nlayers, renderer);
-
-                allocation
-            };
-
-            self.upload_allocation(bytes, &allocation, renderer);
-            let id = self.store.insert((allocation, key.clone()));
-            self.layers[allocation.layer].insert_index(id);
-            self.lookup.insert(key.clone(), id);
-            self.cache.push(id, 1);
-            Some((id, allocation))
+            return Ok(self
+                .store
+                .get(id)
+                .map(|(allocation, _)| (id, *allocation)));
+        }
+
+        let Some(allocation) =
+            self.place(bytes, width, height, data, renderer)?
+        else {
+            return Ok(None);
+        };
+
+        let id = self.store.insert((allocation, key.clone()));
+        self.layers[allocation.layer].insert_index(id);
+        self.lookup.insert(key, id);
+        self.cache.push(id, 1);
+        Ok(Some((id, allocation)))
+    }
+
+    /// Replaces the Texture stored under key with new bytes.
+    /// The bytes are written into the existing [`Allocation`] when the Width
+    /// and Height fit it exactly, otherwise a new [`Allocation`] is made and
+    /// the old one is freed. The Index stays the same either way.
+    ///
+    /// # Returns
+    /// The Index, the [`Allocation`] and if it was reallocated, in which case
+    /// Objects using the Index must be rebuilt. None if the key does not
+    /// exist or there is no room for the new size.
+    ///
+    /// Returns a [`GraphicsError`] if the bytes do not match
+    /// [`AtlasSet::upload_byte_size`].
+    ///
+    pub fn reupload(
+        &mut self,
+        key: &U,
+        bytes: &[u8],
+        width: u32,
+        height: u32,
+        renderer: &GpuRenderer,
+    ) -> Result<Option<(usize, Allocation<Data>, bool)>, GraphicsError> {
+        let Some(id) = self.lookup(key) else {
+            return Ok(None);
+        };
+        let Some(&(old, _)) = self.store.get(id) else {
+            return Ok(None);
+        };
+        let (block_width, block_height) = self.format.block_dimensions();
+
+        self.validate_upload(bytes, width, height)?;
+
+        if old.size()
+            == (
+                width.next_multiple_of(block_width),
+                height.next_multiple_of(block_height),
+            )
+        {
+            self.upload_allocation(bytes, &old, renderer)?;
+            return Ok(Some((id, old, false)));
+        }
+
+        // Keep the entry from being evicted to make room for itself.
+        self.promote(id);
+
+        let Some(allocation) =
+            self.place(bytes, width, height, old.data, renderer)?
+        else {
+            return Ok(None);
+        };
+
+        self.layers[old.layer].deallocate(id, old.allocation);
+        self.layers[allocation.layer].insert_index(id);
+
+        if let Some((stored, _)) = self.store.get_mut(id) {
+            *stored = allocation;
         }
+
+        Ok(Some((id, allocation, true)))
     }
 
     /// Returns the Width and Height of the [`AtlasSet`] and how many Layers Exist.
@@ -719,9 +1093,192 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         &self.texture_group.bind_group
     }
 
+    /// Returns the [`SamplerSettings`] the Texture gets sampled with.
+    ///
+    pub fn sampler(&self) -> SamplerSettings {
+        self.sampler
+    }
+
+    /// Sets the [`SamplerSettings`] the Texture gets sampled with and
+    /// rebuilds the [`TextureGroup`].
+    ///
+    pub fn set_sampler(
+        &mut self,
+        renderer: &GpuRenderer,
+        sampler: SamplerSettings,
+    ) -> &mut Self {
+        if self.sampler != sampler {
+            self.sampler = sampler;
+            self.update_texture_group(renderer);
+        }
+
+        self
+    }
+
+    /// Sets the [`SamplerSettings`] when building the [`AtlasSet`].
+    /// Use [`SamplerSettings::pixel_art`] for sharp pixel art or
+    /// [`SamplerSettings::linear`] for smooth HD UI.
+    ///
+    pub fn with_sampler(
+        mut self,
+        renderer: &GpuRenderer,
+        sampler: SamplerSettings,
+    ) -> Self {
+        self.set_sampler(renderer, sampler);
+        self
+    }
+
+    /// Returns the amount of mip levels the Texture has.
+    ///
+    pub fn mip_level_count(&self) -> u32 {
+        self.mip_level_count
+    }
+
+    /// Returns the pixels reserved around each new [`Allocation`].
+    ///
+    pub fn padding(&self) -> u32 {
+        self.padding
+    }
+
+    /// Sets the pixels reserved around each new [`Allocation`] to stop
+    /// linear filtering or sub pixel positions from bleeding neighbouring
+    /// Textures in. Existing Allocations keep their padding.
+    ///
+    /// [`Allocation::rect`] keeps returning the rectangle of the Texture
+    /// itself so uv coordinates do not change.
+    ///
+    pub fn set_padding(&mut self, padding: u32) -> &mut Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Sets the padding when building the [`AtlasSet`].
+    /// See [`AtlasSet::set_padding`].
+    ///
+    pub fn with_padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Returns if the padding repeats the edge pixels.
+    ///
+    pub fn extrude(&self) -> bool {
+        self.extrude
+    }
+
+    /// Sets if the padding repeats the edge pixels of each upload or is left
+    /// transparent. Defaults to true.
+    ///
+    pub fn set_extrude(&mut self, extrude: bool) -> &mut Self {
+        self.extrude = extrude;
+        self
+    }
+
+    /// Enables mipmapping so scaled down sprites do not shimmer.
+    /// Recreates the Texture with the mip levels and generates them from the
+    /// existing layers. Each upload afterwards regenerates the mip levels it
+    /// covers. A mip_level_count of 1 disables mipmapping.
+    ///
+    /// # Arguments
+    /// - mip_level_count: Amount of mip levels including the full size one.
+    ///   Limited to what the [`AtlasSet`] size allows.
+    /// - padding: Pixels reserved around each new Allocation so
+    ///   neighbours do not bleed into each other in smaller levels. Should
+    ///   be at least 2 to the power of mip_level_count - 1 to fully avoid
+    ///   bleeding in the smallest level. See [`AtlasSet::set_padding`].
+    ///
+    pub fn set_mipmaps(
+        &mut self,
+        renderer: &GpuRenderer,
+        mip_level_count: u32,
+        padding: u32,
+    ) -> Result<(), GraphicsError> {
+        let mip_level_count = mip_level_count.clamp(1, self.size.ilog2() + 1);
+
+        if mip_level_count > 1
+            && !renderer
+                .texture_format_features(self.format)
+                .allowed_usages
+                .contains(TextureUsages::RENDER_ATTACHMENT)
+        {
+            return Err(GraphicsError::UnsupportedFormat(self.format));
+        }
+
+        self.padding = padding;
+
+        if mip_level_count == self.mip_level_count {
+            return Ok(());
+        }
+
+        self.mip_level_count = mip_level_count;
+        self.mipmaps = (mip_level_count > 1)
+            .then(|| MipmapGenerator::new(renderer, self.format));
+
+        let texture = self.create_texture(renderer, self.layers.len() as u32);
+        let mut encoder = renderer.device().create_command_encoder(
+            &wgpu::CommandEncoderDescriptor {
+                label: Some("Texture command encoder"),
+            },
+        );
+
+        for layer in 0..self.layers.len() as u32 {
+            self.copy_layer(&mut encoder, &texture, layer, layer);
+        }
+
+        self.texture = texture;
+
+        for layer in 0..self.layers.len() {
+            self.generate_mipmaps(
+                renderer,
+                &mut encoder,
+                layer,
+                (0, 0, self.size, self.size),
+            );
+        }
+
+        renderer.queue().submit(std::iter::once(encoder.finish()));
+        self.update_texture_group(renderer);
+        Ok(())
+    }
+
+    /// Enables mipmapping when building the [`AtlasSet`].
+    /// See [`AtlasSet::set_mipmaps`].
+    ///
+    pub fn with_mipmaps(
+        mut self,
+        renderer: &GpuRenderer,
+        mip_level_count: u32,
+        padding: u32,
+    ) -> Result<Self, GraphicsError> {
+        self.set_mipmaps(renderer, mip_level_count, padding)?;
+        Ok(self)
+    }
+
     /// Returns a [`BindGroup`] Reference to the AtlasSets Texture Binding.
     pub fn with_deallocations_limit(mut self, limit: usize) -> Self {
         self.deallocations_limit = limit;
         self
     }
 }
+
+/// Returns the smallest size that is a whole amount of blocks both wide and
+/// high for the format. 1 for uncompressed formats.
+///
+fn block_alignment(format: wgpu::TextureFormat) -> u32 {
+    let (width, height) = format.block_dimensions();
+    let (mut a, mut b) = (width, height);
+
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    width / a * height
+}
+
+impl<U: Hash + Eq + Clone, Data: Copy + Default> AsTextureGroup
+    for AtlasSet<U, Data>
+{
+    fn texture_group(&self) -> &TextureGroup {
+        &self.texture_group
+    }
+}
@@ -8,18 +8,44 @@ pub struct Allocation<Data: Copy + Default = i32> {
     pub layer: usize,
     /// Store any Extra data per Allocation.
     pub data: Data,
+    /// Pixels reserved around the Texture on each side. The allocation
+    /// rectangle includes this padding while position, size and rect do not.
+    pub padding: u32,
 }
 
 impl<Data: Copy + Default> Allocation<Data> {
     pub fn position(&self) -> (u32, u32) {
         let rectangle = &self.allocation.rectangle;
 
-        (rectangle.min.x as u32, rectangle.min.y as u32)
+        (
+            rectangle.min.x as u32 + self.padding,
+            rectangle.min.y as u32 + self.padding,
+        )
     }
 
     pub fn rect(&self) -> (u32, u32, u32, u32) {
+        let (x, y) = self.position();
+        let (width, height) = self.size();
+
+        (x, y, width, height)
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        let size = self.allocation.rectangle.size();
+
+        (
+            (size.width as u32).saturating_sub(self.padding * 2),
+            (size.height as u32).saturating_sub(self.padding * 2),
+        )
+    }
+
+    /// Returns the X, Y, Width and Height of the allocated space including
+    /// the padding.
+    ///
+    pub fn outer_rect(&self) -> (u32, u32, u32, u32) {
         let rec = &self.allocation.rectangle;
         let size = rec.size();
+
         (
             rec.min.x as u32,
             rec.min.y as u32,
@@ -27,10 +53,60 @@ impl<Data: Copy + Default> Allocation<Data> {
             size.height as u32,
         )
     }
+}
+
+/// Surrounds a Texture's pixels with padding on every side.
+/// When extrude is true the padding repeats the nearest edge pixel so
+/// filtering at the edges does not blend in neighbouring Textures, otherwise
+/// the padding is left transparent.
+///
+pub fn pad_pixels(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    bytes_per_pixel: u32,
+    padding: u32,
+    extrude: bool,
+) -> Vec<u8> {
+    let bpp = bytes_per_pixel as usize;
+    let (width, height, padding) =
+        (width as usize, height as usize, padding as usize);
+    let row = width * bpp;
+    let stride = (width + padding * 2) * bpp;
+    let mut bytes = vec![0u8; stride * (height + padding * 2)];
+
+    if width == 0 || height == 0 {
+        return bytes;
+    }
 
-    pub fn size(&self) -> (u32, u32) {
-        let size = self.allocation.rectangle.size();
+    for (y, src) in pixels.chunks(row).take(height).enumerate() {
+        let start = (y + padding) * stride;
+        let dst = &mut bytes[start..start + stride];
+
+        dst[padding * bpp..padding * bpp + src.len()].copy_from_slice(src);
 
-        (size.width as u32, size.height as u32)
+        if extrude && src.len() == row {
+            let (first, last) = (&src[..bpp], &src[row - bpp..]);
+
+            for x in 0..padding {
+                dst[x * bpp..(x + 1) * bpp].copy_from_slice(first);
+
+                let right = padding + width + x;
+                dst[right * bpp..(right + 1) * bpp].copy_from_slice(last);
+            }
+        }
     }
+
+    if extrude {
+        let (first, last) = (padding * stride, (padding + height - 1) * stride);
+
+        for y in 0..padding {
+            bytes.copy_within(first..first + stride, y * stride);
+
+            let bottom = (padding + height + y) * stride;
+            bytes.copy_within(last..last + stride, bottom);
+        }
+    }
+
+    bytes
 }
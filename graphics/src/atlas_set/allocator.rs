@@ -43,6 +43,14 @@ impl Allocator {
         self.deallocations = self.deallocations.saturating_add(1);
     }
 
+    /// Removes a Allocation that was only made to test if it fits.
+    /// Unlike [`Allocator::deallocate`] this does not count as deallocation.
+    ///
+    pub(crate) fn cancel(&mut self, allocation: guillotiere::Allocation) {
+        self.allocator.deallocate(allocation.id);
+        self.allocations = self.allocations.saturating_sub(1);
+    }
+
     /// If there are no Allocations.
     ///
     pub fn is_empty(&self) -> bool {
@@ -55,6 +63,26 @@ impl Allocator {
         self.deallocations
     }
 
+    /// Returns the Rectangles of all current Allocations.
+    ///
+    pub fn allocated_rectangles(&self) -> Vec<guillotiere::Rectangle> {
+        let mut rectangles = Vec::with_capacity(self.allocations);
+
+        self.allocator
+            .for_each_allocated_rectangle(|_, rect| rectangles.push(*rect));
+        rectangles
+    }
+
+    /// Returns the Rectangles of all free space left.
+    ///
+    pub fn free_rectangles(&self) -> Vec<guillotiere::Rectangle> {
+        let mut rectangles = Vec::new();
+
+        self.allocator
+            .for_each_free_rectangle(|rect| rectangles.push(*rect));
+        rectangles
+    }
+
     /// Creates a new [`Allocator`] layer.
     ///
     pub fn new(size: u32) -> Self {
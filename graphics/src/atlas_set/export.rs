@@ -0,0 +1,136 @@
+use crate::{AtlasSet, GpuRenderer, GraphicsError};
+use image::{ImageFormat, Rgba, RgbaImage};
+use std::{
+    fs,
+    hash::Hash,
+    path::{Path, PathBuf},
+};
+
+/// Controls what gets drawn over the layers when exporting an [`AtlasSet`].
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AtlasExportSettings {
+    /// Color to outline each allocated rectangle with. None to skip.
+    pub allocated: Option<[u8; 4]>,
+    /// Color to outline each free rectangle with. None to skip.
+    pub free: Option<[u8; 4]>,
+}
+
+impl AtlasExportSettings {
+    /// Exports only the layer pixels.
+    ///
+    pub fn plain() -> Self {
+        Self::default()
+    }
+
+    /// Outlines allocated rectangles in magenta and free rectangles in cyan.
+    ///
+    pub fn outlined() -> Self {
+        Self {
+            allocated: Some([255, 0, 255, 255]),
+            free: Some([0, 255, 255, 255]),
+        }
+    }
+}
+
+impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
+    /// Reads back a layer of the [`AtlasSet`] texture into an [`RgbaImage`].
+    /// Single channel atlases like the glyph atlas are exported as grayscale.
+    ///
+    /// This waits on the GPU and is meant for debugging only.
+    ///
+    pub fn export_layer(
+        &self,
+        renderer: &GpuRenderer,
+        layer: usize,
+        settings: AtlasExportSettings,
+    ) -> Result<RgbaImage, GraphicsError> {
+        let mut image =
+            renderer.capture_texture_layer(&self.texture, layer as u32)?;
+
+        if let Some(atlas) = self.layers.get(layer) {
+            if let Some(color) = settings.free {
+                for rect in atlas.allocator.free_rectangles() {
+                    outline_rect(&mut image, rect, Rgba(color));
+                }
+            }
+
+            if let Some(color) = settings.allocated {
+                for rect in atlas.allocator.allocated_rectangles() {
+                    outline_rect(&mut image, rect, Rgba(color));
+                }
+            }
+        }
+
+        Ok(image)
+    }
+
+    /// Reads back every layer of the [`AtlasSet`] texture.
+    /// See [`AtlasSet::export_layer`].
+    ///
+    pub fn export_layers(
+        &self,
+        renderer: &GpuRenderer,
+        settings: AtlasExportSettings,
+    ) -> Result<Vec<RgbaImage>, GraphicsError> {
+        (0..self.layers.len())
+            .map(|layer| self.export_layer(renderer, layer, settings))
+            .collect()
+    }
+
+    /// Saves every layer as `layer_{index}.png` into the directory.
+    /// See [`AtlasSet::export_layer`].
+    ///
+    /// Returns the paths of the written images.
+    ///
+    pub fn save_layers(
+        &self,
+        renderer: &GpuRenderer,
+        dir: impl AsRef<Path>,
+        settings: AtlasExportSettings,
+    ) -> Result<Vec<PathBuf>, GraphicsError> {
+        let dir = dir.as_ref();
+
+        fs::create_dir_all(dir)?;
+
+        let mut paths = Vec::with_capacity(self.layers.len());
+
+        for layer in 0..self.layers.len() {
+            let path = dir.join(format!("layer_{layer}.png"));
+
+            self.export_layer(renderer, layer, settings)?
+                .save_with_format(&path, ImageFormat::Png)?;
+            paths.push(path);
+        }
+
+        Ok(paths)
+    }
+}
+
+/// Draws a one pixel border along the inside of rect.
+///
+fn outline_rect(
+    image: &mut RgbaImage,
+    rect: guillotiere::Rectangle,
+    color: Rgba<u8>,
+) {
+    let (width, height) = image.dimensions();
+    let min_x = (rect.min.x.max(0) as u32).min(width);
+    let min_y = (rect.min.y.max(0) as u32).min(height);
+    let max_x = (rect.max.x.max(0) as u32).min(width);
+    let max_y = (rect.max.y.max(0) as u32).min(height);
+
+    if min_x >= max_x || min_y >= max_y {
+        return;
+    }
+
+    for x in min_x..max_x {
+        image.put_pixel(x, min_y, color);
+        image.put_pixel(x, max_y - 1, color);
+    }
+
+    for y in min_y..max_y {
+        image.put_pixel(min_x, y, color);
+        image.put_pixel(max_x - 1, y, color);
+    }
+}
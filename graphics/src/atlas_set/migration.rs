@@ -68,6 +68,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
                                 allocation: alloc,
                                 layer: *layer_id,
                                 data: allocation.data,
+                                padding: allocation.padding,
                             },
                         ));
                         continue 'outer;
@@ -91,6 +92,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
                             allocation: alloc,
                             layer: layer_id,
                             data: allocation.data,
+                            padding: allocation.padding,
                         },
                     ));
                     continue 'outer;
@@ -116,11 +118,10 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         allocation: &Allocation<Data>,
         encoder: &mut CommandEncoder,
     ) {
-        let (x, y) = allocation.position();
-        let (width, height) = allocation.size();
+        let (x, y, width, height) = allocation.outer_rect();
         let layer = allocation.layer;
 
-        let (o_x, o_y) = old_allocation.position();
+        let (o_x, o_y, _, _) = old_allocation.outer_rect();
         let o_layer = old_allocation.layer;
 
         encoder.copy_texture_to_texture(
@@ -0,0 +1,576 @@
+use crate::{
+    Allocation, Allocator, Atlas, AtlasSet, GpuRenderer, GraphicsError,
+    pad_pixels,
+};
+use image::{ImageFormat, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Placement of a single packed image within an [`AtlasManifest`].
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AtlasManifestEntry {
+    /// Key the image gets stored under within the [`AtlasSet`].
+    pub name: String,
+    /// Index of the packed layer the image is placed in.
+    pub layer: usize,
+    /// X position of the image within the layer, not including padding.
+    pub x: u32,
+    /// Y position of the image within the layer, not including padding.
+    pub y: u32,
+    /// Width of the image.
+    pub width: u32,
+    /// Height of the image.
+    pub height: u32,
+}
+
+/// Describes the layers and image placements created by an [`AtlasPacker`].
+/// Entries are stored in the order they were allocated so the allocator
+/// state can be restored exactly when loaded into an [`AtlasSet`].
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AtlasManifest {
+    /// Width and Height of each layer.
+    pub size: u32,
+    /// Pixels of extruded padding around each image.
+    #[serde(default)]
+    pub padding: u32,
+    /// File names of the layer images relative to the manifest.
+    pub layers: Vec<String>,
+    /// Placement of every packed image.
+    pub entries: Vec<AtlasManifestEntry>,
+}
+
+impl AtlasManifest {
+    /// Loads a [`AtlasManifest`] from a JSON file.
+    ///
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, GraphicsError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// Parses a [`AtlasManifest`] from a JSON string.
+    ///
+    pub fn from_json(json: &str) -> Result<Self, GraphicsError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Converts the [`AtlasManifest`] into a JSON string.
+    ///
+    pub fn to_json(&self) -> Result<String, GraphicsError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Saves the [`AtlasManifest`] as a JSON file.
+    ///
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), GraphicsError> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+}
+
+/// Layer images and [`AtlasManifest`] created by [`AtlasPacker::pack`].
+///
+#[derive(Clone, Debug, Default)]
+pub struct PackedAtlas {
+    /// Placement of every packed image.
+    pub manifest: AtlasManifest,
+    /// Rgba8 pixels of each layer.
+    pub layers: Vec<RgbaImage>,
+}
+
+impl PackedAtlas {
+    /// Loads a [`PackedAtlas`] from a manifest file and the layer images
+    /// it references.
+    ///
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, GraphicsError> {
+        let path = path.as_ref();
+        let manifest = AtlasManifest::from_file(path)?;
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let layers = manifest
+            .layers
+            .iter()
+            .map(|layer| Ok(image::open(dir.join(layer))?.into_rgba8()))
+            .collect::<Result<Vec<_>, GraphicsError>>()?;
+
+        Ok(Self { manifest, layers })
+    }
+
+    /// Saves each layer as `{name}_{layer}.png` and the manifest as
+    /// `{name}.json` into the directory.
+    ///
+    /// Returns the path of the written manifest.
+    ///
+    pub fn save(
+        &mut self,
+        dir: impl AsRef<Path>,
+        name: &str,
+    ) -> Result<PathBuf, GraphicsError> {
+        let dir = dir.as_ref();
+
+        fs::create_dir_all(dir)?;
+        self.manifest.layers.clear();
+
+        for (i, layer) in self.layers.iter().enumerate() {
+            let file = format!("{name}_{i}.png");
+
+            layer.save_with_format(dir.join(&file), ImageFormat::Png)?;
+            self.manifest.layers.push(file);
+        }
+
+        let path = dir.join(format!("{name}.json"));
+
+        self.manifest.save(&path)?;
+        Ok(path)
+    }
+}
+
+/// Packs images into atlas layers ahead of time using the same allocator
+/// as [`AtlasSet`]. This does not need a [`GpuRenderer`] so it can be used
+/// from build scripts or tools.
+///
+/// Images are packed sorted by size and name so the result only depends on
+/// the images and not on the order they were added in.
+///
+pub struct AtlasPacker {
+    /// Width and Height of each layer.
+    size: u32,
+    /// Pixels of extruded padding around each image.
+    padding: u32,
+    /// Images to pack with their key.
+    images: Vec<(String, RgbaImage)>,
+}
+
+impl AtlasPacker {
+    /// Creates a new [`AtlasPacker`].
+    ///
+    /// # Arguments
+    /// - size: Used for both Width and Height of each layer. Must match the
+    ///   size of the [`AtlasSet`] the result gets loaded into.
+    ///
+    pub fn new(size: u32) -> Self {
+        Self {
+            size,
+            padding: 0,
+            images: Vec::new(),
+        }
+    }
+
+    /// Sets the pixels of padding around each image. The padding repeats
+    /// the edge pixels and is kept when loaded into an [`AtlasSet`].
+    /// See [`AtlasSet::set_padding`].
+    ///
+    pub fn set_padding(&mut self, padding: u32) -> &mut Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Returns the pixels of padding around each image.
+    ///
+    pub fn padding(&self) -> u32 {
+        self.padding
+    }
+
+    /// Returns the Width and Height of each layer.
+    ///
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Returns how many images will get packed.
+    ///
+    pub fn len(&self) -> usize {
+        self.images.len()
+    }
+
+    /// If no images were added yet.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.images.is_empty()
+    }
+
+    /// Adds an image to be packed under the key name.
+    ///
+    pub fn add_image(
+        &mut self,
+        name: impl Into<String>,
+        image: RgbaImage,
+    ) -> &mut Self {
+        self.images.push((name.into(), image));
+        self
+    }
+
+    /// Loads an image file and adds it to be packed under the key name.
+    ///
+    pub fn add_file(
+        &mut self,
+        name: impl Into<String>,
+        path: impl AsRef<Path>,
+    ) -> Result<&mut Self, GraphicsError> {
+        let image = image::open(path)?.into_rgba8();
+
+        Ok(self.add_image(name, image))
+    }
+
+    /// Recursively loads every image within the directory.
+    /// Each image is keyed by its path relative to the directory using `/`
+    /// as separator, for example `tiles/grass.png`.
+    ///
+    pub fn add_directory(
+        &mut self,
+        dir: impl AsRef<Path>,
+    ) -> Result<&mut Self, GraphicsError> {
+        let dir = dir.as_ref();
+        let mut files = Vec::new();
+
+        collect_images(dir, &mut files)?;
+
+        for path in files {
+            let name = path
+                .strip_prefix(dir)
+                .unwrap_or(&path)
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            self.add_file(name, &path)?;
+        }
+
+        Ok(self)
+    }
+
+    /// Packs all added images into layers.
+    ///
+    /// Errors with [`GraphicsError::ImageTooLarge`] if an image is larger
+    /// than a layer.
+    ///
+    pub fn pack(&self) -> Result<PackedAtlas, GraphicsError> {
+        let mut order: Vec<&(String, RgbaImage)> = self.images.iter().collect();
+
+        order.sort_by(|(a_name, a), (b_name, b)| {
+            b.height()
+                .cmp(&a.height())
+                .then(b.width().cmp(&a.width()))
+                .then(a_name.cmp(b_name))
+        });
+
+        let mut allocators: Vec<Allocator> = Vec::new();
+        let mut packed = PackedAtlas {
+            manifest: AtlasManifest {
+                size: self.size,
+                padding: self.padding,
+                layers: Vec::new(),
+                entries: Vec::with_capacity(order.len()),
+            },
+            layers: Vec::new(),
+        };
+
+        for (name, image) in order {
+            let (width, height) = image.dimensions();
+            let padding = self.padding;
+            let (outer_width, outer_height) =
+                (width + padding * 2, height + padding * 2);
+
+            if outer_width > self.size || outer_height > self.size {
+                return Err(GraphicsError::ImageTooLarge(
+                    name.clone(),
+                    width,
+                    height,
+                ));
+            }
+
+            let found =
+                allocators.iter_mut().enumerate().find_map(|(i, layer)| {
+                    layer
+                        .allocate(outer_width, outer_height)
+                        .map(|alloc| (i, alloc))
+                });
+
+            let (layer, allocation) = match found {
+                Some(found) => found,
+                None => {
+                    let mut allocator = Allocator::new(self.size);
+                    let allocation = allocator
+                        .allocate(outer_width, outer_height)
+                        .ok_or_else(|| {
+                            GraphicsError::ImageTooLarge(
+                                name.clone(),
+                                width,
+                                height,
+                            )
+                        })?;
+
+                    allocators.push(allocator);
+                    packed.layers.push(RgbaImage::new(self.size, self.size));
+                    (allocators.len() - 1, allocation)
+                }
+            };
+
+            let (x, y) = (
+                allocation.rectangle.min.x as u32,
+                allocation.rectangle.min.y as u32,
+            );
+
+            if padding > 0 {
+                let bytes =
+                    pad_pixels(image.as_raw(), width, height, 4, padding, true);
+                let padded =
+                    RgbaImage::from_raw(outer_width, outer_height, bytes)
+                        .unwrap_or_default();
+
+                image::imageops::replace(
+                    &mut packed.layers[layer],
+                    &padded,
+                    x as i64,
+                    y as i64,
+                );
+            } else {
+                image::imageops::replace(
+                    &mut packed.layers[layer],
+                    image,
+                    x as i64,
+                    y as i64,
+                );
+            }
+
+            packed.manifest.entries.push(AtlasManifestEntry {
+                name: name.clone(),
+                layer,
+                x: x + padding,
+                y: y + padding,
+                width,
+                height,
+            });
+        }
+
+        Ok(packed)
+    }
+}
+
+/// Gathers all image files within dir and its sub directories sorted by path.
+///
+fn collect_images(
+    dir: &Path,
+    files: &mut Vec<PathBuf>,
+) -> Result<(), GraphicsError> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            collect_images(&path, files)?;
+        } else if ImageFormat::from_path(&path).is_ok() {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+impl<Data: Copy + Default> AtlasSet<String, Data> {
+    /// Loads a [`PackedAtlas`] from a manifest file and uploads it.
+    /// See [`AtlasSet::load_packed`].
+    ///
+    pub fn load_manifest(
+        &mut self,
+        path: impl AsRef<Path>,
+        renderer: &GpuRenderer,
+    ) -> Result<Vec<usize>, GraphicsError> {
+        let packed = PackedAtlas::from_file(path)?;
+
+        self.load_packed(&packed, renderer)
+    }
+
+    /// Uploads the layers of a [`PackedAtlas`] and restores the `store` and
+    /// `lookup` tables from its manifest without decoding each image.
+    ///
+    /// Each packed layer is placed into an empty layer or a new layer gets
+    /// added. The allocator state is rebuilt so later uploads can still use
+    /// the free space of the packed layers. Keys that already exist keep
+    /// their current [`crate::Allocation`].
+    ///
+    /// Returns the Index of each entry in manifest order.
+    ///
+    pub fn load_packed(
+        &mut self,
+        packed: &PackedAtlas,
+        renderer: &GpuRenderer,
+    ) -> Result<Vec<usize>, GraphicsError> {
+        let manifest = &packed.manifest;
+
+        if manifest.size != self.size {
+            return Err(GraphicsError::ManifestMismatch(format!(
+                "manifest size {} does not match atlas size {}",
+                manifest.size, self.size
+            )));
+        }
+
+        if !matches!(
+            self.format,
+            wgpu::TextureFormat::Rgba8UnormSrgb
+                | wgpu::TextureFormat::Rgba8Unorm
+        ) {
+            return Err(GraphicsError::UnsupportedFormat(self.format));
+        }
+
+        if let Some(layer) = packed
+            .layers
+            .iter()
+            .find(|layer| layer.dimensions() != (self.size, self.size))
+        {
+            return Err(GraphicsError::ManifestMismatch(format!(
+                "layer image of {}x{} does not match atlas size {}",
+                layer.width(),
+                layer.height(),
+                self.size
+            )));
+        }
+
+        // Replay the allocations in packing order on fresh allocators first
+        // so a mismatching manifest leaves the AtlasSet untouched.
+        let mut allocators: Vec<Allocator> = (0..packed.layers.len())
+            .map(|_| Allocator::new(self.size))
+            .collect();
+
+        let padding = manifest.padding;
+
+        for entry in &manifest.entries {
+            let placed = allocators
+                .get_mut(entry.layer)
+                .and_then(|layer| {
+                    layer.allocate(
+                        entry.width + padding * 2,
+                        entry.height + padding * 2,
+                    )
+                })
+                .is_some_and(|allocation| {
+                    allocation.rectangle.min.x as u32 + padding == entry.x
+                        && allocation.rectangle.min.y as u32 + padding
+                            == entry.y
+                });
+
+            if !placed {
+                return Err(GraphicsError::ManifestMismatch(format!(
+                    "{} could not be placed at {}x{} on layer {}",
+                    entry.name, entry.x, entry.y, entry.layer
+                )));
+            }
+        }
+
+        // Reuse empty layers and check the layer budget before adding any.
+        let mut targets: Vec<usize> = self
+            .layers
+            .iter()
+            .enumerate()
+            .filter(|(_, layer)| !layer.migrating && layer.allocator.is_empty())
+            .map(|(i, _)| i)
+            .take(packed.layers.len())
+            .collect();
+        let missing = packed.layers.len() - targets.len();
+        let nlayers = self.layers.len();
+
+        if missing > 0 && nlayers + missing >= self.max_layers {
+            return Err(GraphicsError::AtlasMaxLayers);
+        }
+
+        for _ in 0..missing {
+            self.layers.push(Atlas::new(self.size));
+
+            if let Some(migrating) = &mut self.migration {
+                migrating.avaliable.push(self.layers.len() - 1);
+            }
+
+            targets.push(self.layers.len() - 1);
+        }
+
+        self.grow(self.layers.len() - nlayers, renderer);
+
+        let mut encoder = renderer.device().create_command_encoder(
+            &wgpu::CommandEncoderDescriptor {
+                label: Some("Mipmap command encoder"),
+            },
+        );
+
+        for (layer, &target) in packed.layers.iter().zip(&targets) {
+            renderer.queue().write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &self.texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d {
+                        x: 0,
+                        y: 0,
+                        z: target as u32,
+                    },
+                    aspect: wgpu::TextureAspect::All,
+                },
+                layer.as_raw(),
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(4 * self.size),
+                    rows_per_image: Some(self.size),
+                },
+                wgpu::Extent3d {
+                    width: self.size,
+                    height: self.size,
+                    depth_or_array_layers: 1,
+                },
+            );
+            self.generate_mipmaps(
+                renderer,
+                &mut encoder,
+                target,
+                (0, 0, self.size, self.size),
+            );
+        }
+
+        renderer.queue().submit(std::iter::once(encoder.finish()));
+
+        for &target in &targets {
+            self.layers[target].clear();
+        }
+
+        // The allocators are cleared so replaying the allocations gives the
+        // same rectangles as the packer and restores the allocator state.
+        let mut ids = Vec::with_capacity(manifest.entries.len());
+
+        for entry in &manifest.entries {
+            let target = targets[entry.layer];
+            let Some(allocation) = self.layers[target].allocate(
+                entry.width + padding * 2,
+                entry.height + padding * 2,
+            ) else {
+                return Err(GraphicsError::ManifestMismatch(format!(
+                    "{} could not be placed on layer {}",
+                    entry.name, entry.layer
+                )));
+            };
+
+            if let Some(&id) = self.lookup.get(&entry.name) {
+                self.layers[target].allocator.cancel(allocation);
+                ids.push(id);
+                continue;
+            }
+
+            let allocation = Allocation {
+                allocation,
+                layer: target,
+                data: Data::default(),
+                padding,
+            };
+            let id = self.store.insert((allocation, entry.name.clone()));
+
+            self.layers[target].insert_index(id);
+            self.lookup.insert(entry.name.clone(), id);
+            self.cache.push(id, 1);
+            ids.push(id);
+        }
+
+        Ok(ids)
+    }
+}
@@ -0,0 +1,275 @@
+use crate::{AHashMap, Allocation, AtlasSet, GpuRenderer, GraphicsError};
+use std::hash::Hash;
+
+impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
+    /// Amount of layers the texture array must keep. Gl can not bind
+    /// an array texture with a single layer.
+    ///
+    fn min_layers(renderer: &GpuRenderer) -> usize {
+        if renderer.backend == wgpu::Backend::Gl {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Returns true once more layers are empty than the layer_free_limit
+    /// allows, meaning [`AtlasSet::shrink`] can free GPU memory.
+    ///
+    pub fn should_shrink(&self) -> bool {
+        self.layers
+            .iter()
+            .filter(|layer| layer.allocator.is_empty())
+            .count()
+            > self.layer_free_limit
+    }
+
+    /// Tries to move every allocation of a layer into the other layers.
+    /// Returns the new allocations or None if they did not all fit, in
+    /// which case nothing was changed.
+    ///
+    fn evacuate_layer(
+        &mut self,
+        layer_id: usize,
+        removed: &[bool],
+    ) -> Option<Vec<(usize, Allocation<Data>)>> {
+        let ids: Vec<usize> =
+            self.layers[layer_id].allocated.iter().copied().collect();
+        let mut moved = Vec::with_capacity(ids.len());
+
+        'outer: for id in ids {
+            let Some((allocation, _)) = self.store.get(id) else {
+                continue;
+            };
+            let allocation = *allocation;
+            let (_, _, width, height) = allocation.outer_rect();
+
+            for (target, layer) in self.layers.iter_mut().enumerate() {
+                if target == layer_id || removed[target] {
+                    continue;
+                }
+
+                if let Some(new) = layer.allocator.allocate(width, height) {
+                    moved.push((
+                        id,
+                        Allocation {
+                            allocation: new,
+                            layer: target,
+                            data: allocation.data,
+                            padding: allocation.padding,
+                        },
+                    ));
+                    continue 'outer;
+                }
+            }
+
+            // Not everything fits so undo the allocations made so far.
+            for (_, allocation) in moved {
+                self.layers[allocation.layer]
+                    .allocator
+                    .cancel(allocation.allocation);
+            }
+
+            return None;
+        }
+
+        Some(moved)
+    }
+
+    /// Shrinks the [`AtlasSet`] to free GPU memory.
+    ///
+    /// # Strategy
+    /// Allocations of the least used layers get moved into the free space of
+    /// the other layers until no further layer can be emptied. Empty layers
+    /// are then released and a smaller texture array is created holding only
+    /// the remaining layers. Any pending migration from
+    /// [`AtlasSet::defragment`] is cancelled once layers are removed, as they
+    /// are compacted here instead.
+    ///
+    /// # Suggested usage
+    /// Call this after unloading many textures, for example on a zone change,
+    /// or when [`AtlasSet::should_shrink`] returns true.
+    ///
+    /// # Returns
+    /// The Index and new [`Allocation`] of every allocation that changed its
+    /// position or layer. Objects using them must be rebuilt.
+    ///
+    pub fn shrink(
+        &mut self,
+        renderer: &GpuRenderer,
+    ) -> Result<Vec<(usize, Allocation<Data>)>, GraphicsError> {
+        let min_layers = Self::min_layers(renderer);
+
+        let mut removed = vec![false; self.layers.len()];
+        let mut remaining = self.layers.len();
+        let mut moved: AHashMap<usize, Allocation<Data>> = AHashMap::default();
+
+        // Empty layers can be released right away.
+        for (id, layer) in self.layers.iter().enumerate() {
+            if remaining > min_layers && layer.allocator.is_empty() {
+                removed[id] = true;
+                remaining -= 1;
+            }
+        }
+
+        // Try to empty the least used layers into the others.
+        let mut candidates: Vec<(usize, u64)> = self
+            .layers
+            .iter()
+            .enumerate()
+            .filter(|(id, _)| !removed[*id])
+            .map(|(id, layer)| {
+                let area = layer
+                    .allocator
+                    .allocated_rectangles()
+                    .iter()
+                    .map(|rect| rect.area().max(0) as u64)
+                    .sum();
+
+                (id, area)
+            })
+            .collect();
+
+        candidates.sort_by_key(|&(id, area)| (area, id));
+
+        for (layer_id, _) in candidates {
+            if remaining <= min_layers {
+                break;
+            }
+
+            let Some(evacuated) = self.evacuate_layer(layer_id, &removed)
+            else {
+                continue;
+            };
+
+            for (id, allocation) in evacuated {
+                self.layers[allocation.layer].insert_index(id);
+
+                if let Some((old, _)) = self.store.get_mut(id) {
+                    moved.entry(id).or_insert(*old);
+                    *old = allocation;
+                }
+            }
+
+            self.layers[layer_id].clear();
+            removed[layer_id] = true;
+            remaining -= 1;
+        }
+
+        if remaining == self.layers.len() {
+            return Ok(Vec::new());
+        }
+
+        // Layers get removed so their indexs in the migration are stale.
+        if let Some(task) = self.migration.take() {
+            for id in task.migrating {
+                if let Some(layer) = self.layers.get_mut(id) {
+                    layer.migrating = false;
+                }
+            }
+        }
+
+        // Map the old layer indexs to the new compacted ones.
+        let mut remap = vec![usize::MAX; self.layers.len()];
+        let mut next = 0;
+
+        for (id, is_removed) in removed.iter().enumerate() {
+            if !is_removed {
+                remap[id] = next;
+                next += 1;
+            }
+        }
+
+        let texture = self.create_texture(renderer, remaining as u32);
+        let mut encoder = renderer.device().create_command_encoder(
+            &wgpu::CommandEncoderDescriptor {
+                label: Some("Texture command encoder"),
+            },
+        );
+
+        // Copy the kept layers whole, then the moved allocations from their
+        // old place in the old texture into the new one.
+        for (old, &new) in remap.iter().enumerate() {
+            if new == usize::MAX {
+                continue;
+            }
+
+            self.copy_layer(&mut encoder, &texture, old as u32, new as u32);
+        }
+
+        for (&id, old) in &moved {
+            let Some((allocation, _)) = self.store.get(id) else {
+                continue;
+            };
+            let (o_x, o_y, _, _) = old.outer_rect();
+            let (x, y, width, height) = allocation.outer_rect();
+
+            encoder.copy_texture_to_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &self.texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d {
+                        x: o_x,
+                        y: o_y,
+                        z: old.layer as u32,
+                    },
+                    aspect: wgpu::TextureAspect::All,
+                },
+                wgpu::TexelCopyTextureInfo {
+                    texture: &texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d {
+                        x,
+                        y,
+                        z: remap[allocation.layer] as u32,
+                    },
+                    aspect: wgpu::TextureAspect::All,
+                },
+                wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+
+        self.texture = texture;
+
+        // The moved allocations only copied the full size level.
+        for &id in moved.keys() {
+            if let Some((allocation, _)) = self.store.get(id) {
+                self.generate_mipmaps(
+                    renderer,
+                    &mut encoder,
+                    remap[allocation.layer],
+                    allocation.outer_rect(),
+                );
+            }
+        }
+
+        renderer.queue().submit(std::iter::once(encoder.finish()));
+
+        // Drop the removed layers and update every allocation whose layer
+        // index changed.
+        let mut index = 0;
+
+        self.layers.retain(|_| {
+            index += 1;
+            !removed[index - 1]
+        });
+
+        let mut changed = Vec::new();
+
+        for (id, (allocation, _)) in self.store.iter_mut() {
+            let layer = remap[allocation.layer];
+
+            if layer != allocation.layer || moved.contains_key(&id) {
+                allocation.layer = layer;
+                changed.push((id, *allocation));
+            }
+        }
+
+        self.update_texture_group(renderer);
+        Ok(changed)
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{Atlas, AtlasSet};
+use std::hash::Hash;
+
+/// Usage information of a single [`Atlas`] layer.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct AtlasLayerStats {
+    /// Amount of allocations within the layer.
+    pub allocations: usize,
+    /// Pixels covered by allocations.
+    pub used_area: u64,
+    /// Pixels not covered by allocations.
+    pub free_area: u64,
+    /// Used area divided by the layers total area. From 0.0 to 1.0.
+    pub occupancy: f32,
+    /// Width and Height of the largest free rectangle.
+    pub largest_free: (u32, u32),
+    /// Deallocations since the layer was created or cleared.
+    pub deallocations: usize,
+    /// If allocations are being migrated out of the layer.
+    pub migrating: bool,
+}
+
+impl AtlasLayerStats {
+    /// Gathers the [`AtlasLayerStats`] of a [`Atlas`] layer.
+    ///
+    pub fn new(layer: &Atlas, size: u32) -> Self {
+        let area = |rect: &guillotiere::Rectangle| {
+            let size = rect.size();
+            size.width.max(0) as u64 * size.height.max(0) as u64
+        };
+        let allocated = layer.allocator.allocated_rectangles();
+        let free = layer.allocator.free_rectangles();
+        let used_area: u64 = allocated.iter().map(area).sum();
+        let total = size as u64 * size as u64;
+        let largest_free = free
+            .iter()
+            .max_by_key(|rect| area(rect))
+            .map(|rect| (rect.width() as u32, rect.height() as u32))
+            .unwrap_or_default();
+
+        Self {
+            allocations: allocated.len(),
+            used_area,
+            free_area: total.saturating_sub(used_area),
+            occupancy: if total == 0 {
+                0.0
+            } else {
+                used_area as f32 / total as f32
+            },
+            largest_free,
+            deallocations: layer.deallocations(),
+            migrating: layer.migrating,
+        }
+    }
+}
+
+/// Usage and memory information of a [`AtlasSet`].
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtlasStats {
+    /// Width and Height of each layer.
+    pub size: u32,
+    /// Format of the Texture.
+    pub format: wgpu::TextureFormat,
+    /// Stats of each layer.
+    pub layers: Vec<AtlasLayerStats>,
+    /// Amount of stored allocations.
+    pub allocations: usize,
+    /// Amount of entries within the LRU cache.
+    pub cached: usize,
+    /// Amount of allocations used since the last trim.
+    pub last_used: usize,
+    /// Bytes the texture array uses in GPU memory.
+    pub texture_bytes: u64,
+    /// Layers still waiting to be migrated by [`AtlasSet::defragment`].
+    pub pending_migrations: usize,
+}
+
+impl AtlasStats {
+    /// Pixels covered by allocations over all layers.
+    ///
+    pub fn used_area(&self) -> u64 {
+        self.layers.iter().map(|layer| layer.used_area).sum()
+    }
+
+    /// Pixels not covered by allocations over all layers.
+    ///
+    pub fn free_area(&self) -> u64 {
+        self.layers.iter().map(|layer| layer.free_area).sum()
+    }
+
+    /// Used area divided by the total area of all layers. From 0.0 to 1.0.
+    ///
+    pub fn occupancy(&self) -> f32 {
+        let total = self.used_area() + self.free_area();
+
+        if total == 0 {
+            0.0
+        } else {
+            self.used_area() as f32 / total as f32
+        }
+    }
+
+    /// Amount of layers without any allocations.
+    ///
+    pub fn empty_layers(&self) -> usize {
+        self.layers
+            .iter()
+            .filter(|layer| layer.allocations == 0)
+            .count()
+    }
+}
+
+impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
+    /// Returns the [`AtlasStats`] of the [`AtlasSet`]. This walks every
+    /// layers allocator so avoid calling it every frame with many layers.
+    ///
+    pub fn stats(&self) -> AtlasStats {
+        AtlasStats {
+            size: self.size,
+            format: self.format,
+            layers: self
+                .layers
+                .iter()
+                .map(|layer| AtlasLayerStats::new(layer, self.size))
+                .collect(),
+            allocations: self.store.len(),
+            cached: self.cache.len(),
+            last_used: self.last_used.len(),
+            texture_bytes: self.texture_bytes(),
+            pending_migrations: self
+                .migration
+                .as_ref()
+                .map(|task| task.migrating.len())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the bytes the texture array uses in GPU memory.
+    ///
+    pub fn texture_bytes(&self) -> u64 {
+        let block_size = self.format.block_copy_size(None).unwrap_or(4) as u64;
+        let (block_width, block_height) = self.format.block_dimensions();
+        let layers = self.texture.depth_or_array_layers() as u64;
+
+        (0..self.texture.mip_level_count())
+            .map(|level| {
+                let width = (self.texture.width() >> level)
+                    .max(1)
+                    .div_ceil(block_width) as u64;
+                let height = (self.texture.height() >> level)
+                    .max(1)
+                    .div_ceil(block_height) as u64;
+
+                width * height * block_size * layers
+            })
+            .sum()
+    }
+}
@@ -28,6 +28,8 @@ pub enum GraphicsError {
     #[error(transparent)]
     Surface(#[from] wgpu::SurfaceError),
     #[error(transparent)]
+    CreateSurface(#[from] wgpu::CreateSurfaceError),
+    #[error(transparent)]
     WGpu(#[from] wgpu::Error),
     #[error(transparent)]
     Device(#[from] wgpu::RequestDeviceError),
@@ -35,6 +37,20 @@ pub enum GraphicsError {
     Adapter(#[from] wgpu::RequestAdapterError),
     #[error(transparent)]
     ImageError(#[from] image::ImageError),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Ktx2(#[from] ktx2::ParseError),
+    #[error(transparent)]
+    Dds(#[from] ddsfile::Error),
+    #[error(transparent)]
+    Xml(#[from] roxmltree::Error),
+    #[error(transparent)]
+    Base64(#[from] base64::DecodeError),
+    #[error(transparent)]
+    BufferAsync(#[from] wgpu::BufferAsyncError),
+    #[error(transparent)]
+    Poll(#[from] wgpu::PollError),
     #[error("Image atlas has no more space.")]
     AtlasFull,
     #[error(transparent)]
@@ -57,4 +73,28 @@ pub enum GraphicsError {
         "No Adpater could be created by the system. Maybe its not supported?"
     )]
     AdapterNotFound,
+    #[error("The texture format {0:?} is not supported for this use.")]
+    UnsupportedFormat(wgpu::TextureFormat),
+    #[error("A multisample count of {0} is not supported by the adapter.")]
+    UnsupportedSampleCount(u32),
+    #[error("The present mode {0:?} is not supported by the surface.")]
+    UnsupportedPresentMode(wgpu::PresentMode),
+    #[error("The alpha mode {0:?} is not supported by the surface.")]
+    UnsupportedAlphaMode(wgpu::CompositeAlphaMode),
+    #[error("A maximum frame latency of {0} is not supported.")]
+    UnsupportedFrameLatency(u32),
+    #[error("None of the surface formats {0:?} are supported by the renderer.")]
+    NoSurfaceFormat(Vec<wgpu::TextureFormat>),
+    #[error("The image {0} of {1}x{2} does not fit into the atlas.")]
+    ImageTooLarge(String, u32, u32),
+    #[error("The upload needs {0} bytes but the buffer holds {1} bytes.")]
+    BufferSizeMismatch(usize, usize),
+    #[error("The texture file {0} is not supported: {1}")]
+    UnsupportedTextureFile(String, String),
+    #[error("The atlas manifest does not match the atlas: {0}")]
+    ManifestMismatch(String),
+    #[error("The shader could not be compiled: {0}")]
+    InvalidShader(String),
+    #[error("The Tiled map {0} could not be imported: {1}")]
+    InvalidTiledMap(String, String),
 }
@@ -1,14 +1,33 @@
 use crate::{
-    BufferLayout, GpuDevice, LayoutStorage, PipeLineLayout, StaticVertexBuffer,
-    SystemLayout, TextVertex, TextureLayout,
+    BlendMode, BufferLayout, GpuDevice, LayoutStorage, PipeLineLayout,
+    StaticVertexBuffer, SystemLayout, TextVertex, TextureLayout,
+    gamma_constants,
 };
 use bytemuck::{Pod, Zeroable};
 
 /// [`crate::Text`] RenderPipeline Layout
 ///
 #[repr(C)]
-#[derive(Clone, Copy, Hash, Pod, Zeroable)]
-pub struct TextRenderPipeline;
+#[derive(Clone, Copy, Default, Hash, Pod, Zeroable)]
+pub struct TextRenderPipeline {
+    blend_mode: u32,
+}
+
+impl TextRenderPipeline {
+    /// Creates the Pipeline Layout key for a [`BlendMode`].
+    ///
+    pub fn new(blend_mode: BlendMode) -> Self {
+        Self {
+            blend_mode: blend_mode as u32,
+        }
+    }
+
+    /// Returns the [`BlendMode`] of the Pipeline.
+    ///
+    pub fn blend_mode(&self) -> BlendMode {
+        BlendMode::from_u32(self.blend_mode)
+    }
+}
 
 impl PipeLineLayout for TextRenderPipeline {
     fn create_layout(
@@ -61,7 +80,10 @@ impl PipeLineLayout for TextRenderPipeline {
                             attributes: &TextVertex::attributes(),
                         },
                     ],
-                    compilation_options: Default::default(),
+                    compilation_options: wgpu::PipelineCompilationOptions {
+                        constants: gamma_constants(surface_format),
+                        ..Default::default()
+                    },
                 },
                 primitive: wgpu::PrimitiveState {
                     topology: wgpu::PrimitiveTopology::TriangleList,
@@ -79,16 +101,19 @@ impl PipeLineLayout for TextRenderPipeline {
                     stencil: wgpu::StencilState::default(),
                     bias: wgpu::DepthBiasState::default(),
                 }),
-                multisample: wgpu::MultisampleState::default(),
+                multisample: gpu_device.multisample_state(),
                 fragment: Some(wgpu::FragmentState {
                     module: &shader,
                     entry_point: Some("fragment"),
                     targets: &[Some(wgpu::ColorTargetState {
                         format: surface_format,
-                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        blend: Some(self.blend_mode().to_blend_state()),
                         write_mask: wgpu::ColorWrites::ALL,
                     })],
-                    compilation_options: Default::default(),
+                    compilation_options: wgpu::PipelineCompilationOptions {
+                        constants: gamma_constants(surface_format),
+                        ..Default::default()
+                    },
                 }),
                 multiview: None,
                 cache: None,
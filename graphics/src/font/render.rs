@@ -1,7 +1,7 @@
 use crate::{
-    AsBufferPass, AtlasSet, GpuRenderer, GraphicsError, InstanceBuffer,
-    OrderedIndex, SetBuffers, StaticVertexBuffer, Text, TextRenderPipeline,
-    TextVertex, Vec2,
+    AsBufferPass, AtlasSet, AtlasStats, BlendMode, GpuRenderer, GraphicsError,
+    InstanceBuffer, OrderedIndex, SetBuffers, StaticVertexBuffer, Text,
+    TextRenderPipeline, TextVertex, Vec2,
 };
 use cosmic_text::{CacheKey, SwashCache};
 use log::{error, warn};
@@ -48,6 +48,24 @@ impl TextAtlas {
         self.emoji.trim();
         self.text.trim();
     }
+
+    /// Returns the [`AtlasStats`] of the Text [`AtlasSet`].
+    ///
+    pub fn text_stats(&self) -> AtlasStats {
+        self.text.stats()
+    }
+
+    /// Returns the [`AtlasStats`] of the Emoji [`AtlasSet`].
+    ///
+    pub fn emoji_stats(&self) -> AtlasStats {
+        self.emoji.stats()
+    }
+
+    /// Returns the bytes both internal [`AtlasSet`]'s use in GPU memory.
+    ///
+    pub fn texture_bytes(&self) -> u64 {
+        self.text.texture_bytes() + self.emoji.texture_bytes()
+    }
 }
 
 /// Instance Buffer Setup for [`Text`].
@@ -55,6 +73,8 @@ impl TextAtlas {
 pub struct TextRenderer {
     pub buffer: InstanceBuffer<TextVertex>,
     pub swash_cache: SwashCache,
+    /// [`BlendMode`] the Renderer draws with.
+    blend_mode: BlendMode,
 }
 
 impl TextRenderer {
@@ -64,9 +84,34 @@ impl TextRenderer {
         Ok(Self {
             buffer: InstanceBuffer::new(renderer.gpu_device(), 1024),
             swash_cache: SwashCache::new(),
+            blend_mode: BlendMode::Alpha,
         })
     }
 
+    /// Creates a new [`TextRenderer`] that draws using a [`BlendMode`].
+    /// Creates the [`TextRenderPipeline`] for the mode if it does not yet exist.
+    ///
+    /// # Arguments
+    /// - blend_mode: [`BlendMode`] used to draw with.
+    ///
+    pub fn with_blend_mode(
+        renderer: &mut GpuRenderer,
+        blend_mode: BlendMode,
+    ) -> Result<Self, GraphicsError> {
+        renderer.create_pipeline(TextRenderPipeline::new(blend_mode));
+
+        let mut this = Self::new(renderer)?;
+
+        this.blend_mode = blend_mode;
+        Ok(this)
+    }
+
+    /// Returns the [`BlendMode`] the Renderer draws with.
+    ///
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
     /// Adds a Buffer [`OrderedIndex`] to the Rendering Store to get processed.
     /// This must be done before [`TextRenderer::finalize`] but after [`Text::update`] in order for it to Render.
     ///
@@ -160,7 +205,11 @@ where
                 self.set_bind_group(2, atlas.emoji.bind_group(), &[]);
                 self.set_vertex_buffer(1, buffer.buffer.instances(None));
                 self.set_pipeline(
-                    renderer.get_pipelines(TextRenderPipeline).unwrap(),
+                    renderer
+                        .get_pipelines(TextRenderPipeline::new(
+                            buffer.blend_mode,
+                        ))
+                        .unwrap(),
                 );
                 self.draw_indexed(
                     0..StaticVertexBuffer::index_count(),
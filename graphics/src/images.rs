@@ -8,7 +8,7 @@ pub use vertex::*;
 
 use crate::{
     AtlasSet, Bounds, CameraType, Color, DrawOrder, FlipStyle, GpuRenderer,
-    Index, OrderedIndex, Vec2, Vec3, Vec4,
+    Index, OrderedIndex, RenderTarget, Vec2, Vec3, Vec4,
 };
 
 /// Basic and Fast Image Rendering Type. Best used for Sprites and Objects in the world.
@@ -44,6 +44,8 @@ pub struct Image {
     pub rotation_angle: f32,
     /// When true tells system to update the buffers.
     pub changed: bool,
+    /// Size of the [`RenderTarget`] the Buffers were last built from.
+    pub target_size: (u32, u32),
 }
 
 impl Image {
@@ -74,6 +76,7 @@ impl Image {
             flip_style: FlipStyle::None,
             rotation_angle: 0.0,
             changed: true,
+            target_size: (0, 0),
         }
     }
 
@@ -221,7 +224,30 @@ impl Image {
             None => return,
         };
 
-        let (u, v, width, height) = allocation.rect();
+        self.write_quad(renderer, allocation.rect(), allocation.layer as i32);
+    }
+
+    /// Updates the [`Image`]'s Buffers to render from a [`RenderTarget`].
+    ///
+    fn create_target_quad(
+        &mut self,
+        renderer: &mut GpuRenderer,
+        target: &RenderTarget,
+    ) {
+        let (width, height) = target.size();
+
+        self.write_quad(renderer, (0, 0, width, height), 0);
+    }
+
+    /// Writes the [`Image`]'s Instance into its Buffer Store.
+    ///
+    fn write_quad(
+        &mut self,
+        renderer: &mut GpuRenderer,
+        rect: (u32, u32, u32, u32),
+        layer: i32,
+    ) {
+        let (u, v, width, height) = rect;
         let tex_data = (
             self.uv.x + u as f32,
             self.uv.y + v as f32,
@@ -238,7 +264,7 @@ impl Image {
             animate: u32::from(self.animate),
             camera_type: self.camera_type as u32,
             time: self.switch_time,
-            layer: allocation.layer as i32,
+            layer,
             flip_style: self.flip_style as u32,
             angle: self.rotation_angle,
         };
@@ -276,4 +302,33 @@ impl Image {
             self.camera_type,
         )
     }
+
+    /// Used to check and update the vertex array using a [`RenderTarget`]
+    /// as the Texture instead of the texture within an [`AtlasSet`].
+    /// Returns a [`OrderedIndex`] used in Rendering.
+    ///
+    pub fn update_with_target(
+        &mut self,
+        renderer: &mut GpuRenderer,
+        target: &RenderTarget,
+    ) -> OrderedIndex {
+        // The uv clamps depend on the Targets size.
+        if self.target_size != target.size() {
+            self.target_size = target.size();
+            self.changed = true;
+        }
+
+        if self.changed {
+            self.create_target_quad(renderer, target);
+            self.changed = false;
+        }
+
+        OrderedIndex::new_with_bounds(
+            self.order,
+            self.store_id,
+            0,
+            self.bounds,
+            self.camera_type,
+        )
+    }
 }
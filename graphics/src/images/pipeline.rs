@@ -1,14 +1,33 @@
 use crate::{
-    BufferLayout, GpuDevice, ImageVertex, LayoutStorage, PipeLineLayout,
-    StaticVertexBuffer, SystemLayout, TextureLayout,
+    BlendMode, BufferLayout, GpuDevice, ImageVertex, LayoutStorage,
+    PipeLineLayout, StaticVertexBuffer, SystemLayout, TextureLayout,
+    gamma_constants,
 };
 use bytemuck::{Pod, Zeroable};
 
 /// [`crate::Image`] RenderPipeline Layout
 ///
 #[repr(C)]
-#[derive(Clone, Copy, Hash, Pod, Zeroable)]
-pub struct ImageRenderPipeline;
+#[derive(Clone, Copy, Default, Hash, Pod, Zeroable)]
+pub struct ImageRenderPipeline {
+    blend_mode: u32,
+}
+
+impl ImageRenderPipeline {
+    /// Creates the Pipeline Layout key for a [`BlendMode`].
+    ///
+    pub fn new(blend_mode: BlendMode) -> Self {
+        Self {
+            blend_mode: blend_mode as u32,
+        }
+    }
+
+    /// Returns the [`BlendMode`] of the Pipeline.
+    ///
+    pub fn blend_mode(&self) -> BlendMode {
+        BlendMode::from_u32(self.blend_mode)
+    }
+}
 
 impl PipeLineLayout for ImageRenderPipeline {
     fn create_layout(
@@ -57,7 +76,10 @@ impl PipeLineLayout for ImageRenderPipeline {
                             attributes: &ImageVertex::attributes(),
                         },
                     ],
-                    compilation_options: Default::default(),
+                    compilation_options: wgpu::PipelineCompilationOptions {
+                        constants: gamma_constants(surface_format),
+                        ..Default::default()
+                    },
                 },
                 primitive: wgpu::PrimitiveState {
                     topology: wgpu::PrimitiveTopology::TriangleList,
@@ -75,16 +97,19 @@ impl PipeLineLayout for ImageRenderPipeline {
                     stencil: wgpu::StencilState::default(),
                     bias: wgpu::DepthBiasState::default(),
                 }),
-                multisample: wgpu::MultisampleState::default(),
+                multisample: gpu_device.multisample_state(),
                 fragment: Some(wgpu::FragmentState {
                     module: &shader,
                     entry_point: Some("fragment"),
                     targets: &[Some(wgpu::ColorTargetState {
                         format: surface_format,
-                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        blend: Some(self.blend_mode().to_blend_state()),
                         write_mask: wgpu::ColorWrites::ALL,
                     })],
-                    compilation_options: Default::default(),
+                    compilation_options: wgpu::PipelineCompilationOptions {
+                        constants: gamma_constants(surface_format),
+                        ..Default::default()
+                    },
                 }),
                 multiview: None,
                 cache: None,
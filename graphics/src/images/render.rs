@@ -1,12 +1,15 @@
 use crate::{
-    AtlasSet, GpuRenderer, GraphicsError, Image, ImageRenderPipeline,
-    ImageVertex, InstanceBuffer, OrderedIndex, StaticVertexBuffer, System,
+    AsTextureGroup, AtlasSet, BlendMode, GpuRenderer, GraphicsError, Image,
+    ImageRenderPipeline, ImageVertex, InstanceBuffer, OrderedIndex,
+    RenderTarget, StaticVertexBuffer, System,
 };
 
 /// Instance Buffer Setup for [`Image`].
 ///
 pub struct ImageRenderer {
     pub buffer: InstanceBuffer<ImageVertex>,
+    /// [`BlendMode`] the Renderer draws with.
+    blend_mode: BlendMode,
 }
 
 impl ImageRenderer {
@@ -15,9 +18,34 @@ impl ImageRenderer {
     pub fn new(renderer: &GpuRenderer) -> Result<Self, GraphicsError> {
         Ok(Self {
             buffer: InstanceBuffer::new(renderer.gpu_device(), 512),
+            blend_mode: BlendMode::Alpha,
         })
     }
 
+    /// Creates a new [`ImageRenderer`] that draws using a [`BlendMode`].
+    /// Creates the [`ImageRenderPipeline`] for the mode if it does not yet exist.
+    ///
+    /// # Arguments
+    /// - blend_mode: [`BlendMode`] used to draw with.
+    ///
+    pub fn with_blend_mode(
+        renderer: &mut GpuRenderer,
+        blend_mode: BlendMode,
+    ) -> Result<Self, GraphicsError> {
+        renderer.create_pipeline(ImageRenderPipeline::new(blend_mode));
+
+        let mut this = Self::new(renderer)?;
+
+        this.blend_mode = blend_mode;
+        Ok(this)
+    }
+
+    /// Returns the [`BlendMode`] the Renderer draws with.
+    ///
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
     /// Adds a Buffer [`OrderedIndex`] to the Rendering Store to get processed.
     /// This must be done before [`ImageRenderer::finalize`] but after [`Image::update`] in order for it to Render.
     ///
@@ -61,6 +89,28 @@ impl ImageRenderer {
         self.add_buffer_store(renderer, index, buffer_layer);
     }
 
+    /// Updates a [`Image`] that draws a [`RenderTarget`] and adds its [`OrderedIndex`]
+    /// to staging using [`ImageRenderer::add_buffer_store`].
+    /// The [`ImageRenderer`] must then be rendered using the [`RenderTarget`]
+    /// so use a separate [`ImageRenderer`] from the ones using an [`AtlasSet`].
+    ///
+    /// # Arguments
+    /// - image: [`Image`] we want to update and prepare for rendering.
+    /// - target: [`RenderTarget`] the [`Image`] needs to render with.
+    /// - buffer_layer: The Buffer Layer we want to add this Object too.
+    ///
+    pub fn image_target_update(
+        &mut self,
+        image: &mut Image,
+        renderer: &mut GpuRenderer,
+        target: &RenderTarget,
+        buffer_layer: usize,
+    ) {
+        let index = image.update_with_target(renderer, target);
+
+        self.add_buffer_store(renderer, index, buffer_layer);
+    }
+
     /// Sets the Instance Buffer to enable Rendering With Scissor Clipping.
     /// This must be Set for the Optional Bounds to be used.
     ///
@@ -76,12 +126,13 @@ where
     Controls: camera::controls::Controls,
 {
     /// Renders the all [`Image`]'s within the buffer layer to screen that have been processed and finalized.
+    /// atlas can be an [`AtlasSet`] or a [`RenderTarget`].
     ///
-    fn render_image(
+    fn render_image<T: AsTextureGroup>(
         &mut self,
         renderer: &'b GpuRenderer,
         buffer: &'b ImageRenderer,
-        atlas: &'b AtlasSet,
+        atlas: &'b T,
         system: &'b System<Controls>,
         buffer_layer: usize,
     );
@@ -92,11 +143,11 @@ where
     'b: 'a,
     Controls: camera::controls::Controls,
 {
-    fn render_image(
+    fn render_image<T: AsTextureGroup>(
         &mut self,
         renderer: &'b GpuRenderer,
         buffer: &'b ImageRenderer,
-        atlas: &'b AtlasSet,
+        atlas: &'b T,
         system: &'b System<Controls>,
         buffer_layer: usize,
     ) {
@@ -107,10 +158,18 @@ where
                 let mut scissor_is_default = true;
 
                 if buffer.buffer.count() > 0 {
-                    self.set_bind_group(1, atlas.bind_group(), &[]);
+                    self.set_bind_group(
+                        1,
+                        &atlas.texture_group().bind_group,
+                        &[],
+                    );
                     self.set_vertex_buffer(1, buffer.buffer.instances(None));
                     self.set_pipeline(
-                        renderer.get_pipelines(ImageRenderPipeline).unwrap(),
+                        renderer
+                            .get_pipelines(ImageRenderPipeline::new(
+                                buffer.blend_mode,
+                            ))
+                            .unwrap(),
                     );
                     for (details, bounds, camera_type) in details {
                         if let Some(bounds) = bounds {
@@ -148,10 +207,14 @@ where
             buffer.buffer.buffers.get(buffer_layer)
         {
             if buffer.buffer.count() > 0 {
-                self.set_bind_group(1, atlas.bind_group(), &[]);
+                self.set_bind_group(1, &atlas.texture_group().bind_group, &[]);
                 self.set_vertex_buffer(1, buffer.buffer.instances(None));
                 self.set_pipeline(
-                    renderer.get_pipelines(ImageRenderPipeline).unwrap(),
+                    renderer
+                        .get_pipelines(ImageRenderPipeline::new(
+                            buffer.blend_mode,
+                        ))
+                        .unwrap(),
                 );
 
                 self.draw_indexed(
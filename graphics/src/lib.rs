@@ -6,6 +6,7 @@ mod images;
 mod lights;
 mod maps;
 mod mesh2d;
+mod post_process;
 mod systems;
 mod textures;
 mod tilesheet;
@@ -20,6 +21,7 @@ pub use images::*;
 pub use lights::*;
 pub use maps::*;
 pub use mesh2d::*;
+pub use post_process::*;
 pub use systems::*;
 pub use textures::*;
 pub use tilesheet::*;
@@ -1,10 +1,10 @@
 use std::{iter, mem};
 
 use crate::{
-    AreaLightLayout, AreaLightRaw, DirLightLayout, DirectionalLightRaw,
-    GpuRenderer, GraphicsError, InstanceBuffer, LightRenderPipeline, Lights,
-    LightsVertex, MAX_AREA_LIGHTS, MAX_DIR_LIGHTS, OrderedIndex,
-    StaticVertexBuffer,
+    AreaLightLayout, AreaLightRaw, BlendMode, DirLightLayout,
+    DirectionalLightRaw, GpuRenderer, GraphicsError, InstanceBuffer,
+    LightRenderPipeline, Lights, LightsVertex, MAX_AREA_LIGHTS, MAX_DIR_LIGHTS,
+    OrderedIndex, StaticVertexBuffer,
 };
 
 use log::warn;
@@ -23,6 +23,8 @@ pub struct LightRenderer {
     area_bind_group: wgpu::BindGroup,
     /// Uniform buffer BindGroup for the array of [`crate::DirectionalLight`]'s.
     dir_bind_group: wgpu::BindGroup,
+    /// [`BlendMode`] the Renderer draws with.
+    blend_mode: BlendMode,
 }
 
 impl LightRenderer {
@@ -98,9 +100,34 @@ impl LightRenderer {
             area_buffer,
             area_bind_group,
             dir_bind_group,
+            blend_mode: BlendMode::Alpha,
         })
     }
 
+    /// Creates a new [`LightRenderer`] that draws using a [`BlendMode`].
+    /// Creates the [`LightRenderPipeline`] for the mode if it does not yet exist.
+    ///
+    /// # Arguments
+    /// - blend_mode: [`BlendMode`] used to draw with.
+    ///
+    pub fn with_blend_mode(
+        renderer: &mut GpuRenderer,
+        blend_mode: BlendMode,
+    ) -> Result<Self, GraphicsError> {
+        renderer.create_pipeline(LightRenderPipeline::new(blend_mode));
+
+        let mut this = Self::new(renderer)?;
+
+        this.blend_mode = blend_mode;
+        Ok(this)
+    }
+
+    /// Returns the [`BlendMode`] the Renderer draws with.
+    ///
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
     /// Adds a Buffer [`OrderedIndex`] to the Rendering Store to get processed.
     /// This must be done before [`LightRenderer::finalize`] but after [`Lights::update`] in order for it to Render.
     ///
@@ -184,7 +211,11 @@ where
                 self.set_bind_group(2, &buffer.dir_bind_group, &[]);
                 self.set_vertex_buffer(1, buffer.buffer.instances(None));
                 self.set_pipeline(
-                    renderer.get_pipelines(LightRenderPipeline).unwrap(),
+                    renderer
+                        .get_pipelines(LightRenderPipeline::new(
+                            buffer.blend_mode,
+                        ))
+                        .unwrap(),
                 );
 
                 self.draw_indexed(
@@ -1,12 +1,20 @@
+mod autotile;
+mod layout;
 mod pipeline;
 mod render;
+mod tiled;
 mod vertex;
+mod world;
 
+pub use autotile::*;
+pub use layout::*;
 pub use pipeline::*;
 pub use render::*;
+pub use tiled::*;
 pub use vertex::*;
+pub use world::*;
 
-use std::iter;
+use std::{iter, slice};
 
 use crate::{
     AtlasSet, CameraType, DrawOrder, GpuRenderer, Index, OrderedIndex, Vec2,
@@ -14,6 +22,8 @@ use crate::{
 };
 use cosmic_text::Color;
 
+/// Layers of the default [`MapLayout`].
+///
 #[allow(dead_code)]
 #[derive(Copy, Clone, PartialEq, PartialOrd)]
 pub enum MapLayers {
@@ -78,6 +88,9 @@ pub struct TileData {
     ///tiles allocation ID within the texture.
     pub id: usize,
     pub color: Color,
+    /// Index of the [`TileAnimation`] within [`Map::animations`] to play
+    /// instead of showing id.
+    pub animation: Option<usize>,
 }
 
 impl Default for TileData {
@@ -85,10 +98,66 @@ impl Default for TileData {
         Self {
             id: 0,
             color: Color::rgba(255, 255, 255, 255),
+            animation: None,
         }
     }
 }
 
+impl TileData {
+    /// Returns the tile allocation IDs the tile shows and the milliseconds
+    /// each is shown. A missing or empty animation falls back to id and an
+    /// animation without a switch time only shows its first frame.
+    ///
+    pub fn frames<'a>(
+        &'a self,
+        animations: &'a [TileAnimation],
+    ) -> (&'a [usize], u32) {
+        match self
+            .animation
+            .and_then(|index| animations.get(index))
+            .filter(|animation| !animation.frames.is_empty())
+        {
+            Some(animation) if animation.switch_time > 0 => {
+                (animation.frames.as_slice(), animation.switch_time)
+            }
+            Some(animation) => (&animation.frames[..1], 0),
+            None if self.id > 0 => (slice::from_ref(&self.id), 0),
+            None => (&[], 0),
+        }
+    }
+
+    /// If the tile shows anything.
+    ///
+    pub fn is_filled(&self, animations: &[TileAnimation]) -> bool {
+        self.color.a() > 0 && !self.frames(animations).0.is_empty()
+    }
+}
+
+/// Animated tile like water or torches. Each frame is a tile's allocation
+/// ID within the texture, animated on the GPU so the [`Map`] does not need
+/// to be rebuilt when the frame changes.
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TileAnimation {
+    /// Tile allocation IDs within the texture in the order they play.
+    pub frames: Vec<usize>,
+    /// Milliseconds each frame is shown.
+    pub switch_time: u32,
+}
+
+impl TileAnimation {
+    /// Creates a new [`TileAnimation`] playing frames switching every
+    /// switch_time in milliseconds.
+    ///
+    pub fn new(frames: Vec<usize>, switch_time: u32) -> Self {
+        Self {
+            frames,
+            switch_time,
+        }
+    }
+}
+
+/// Tile counts of the default [`MapLayout`].
 pub const TILE_COUNT: usize = 9216;
 pub const LOWER_COUNT: usize = 7168;
 pub const UPPER_COUNT: usize = 2048;
@@ -108,8 +177,8 @@ pub struct Map {
     pub stores: [Index; 2],
     /// the draw order of the maps. created when update is called.
     pub orders: [DrawOrder; 2],
-    /// count if any Filled Tiles Exist. this is to optimize out empty maps in rendering.
-    pub filled_tiles: [u16; MapLayers::Count as usize],
+    /// count if any Filled Tiles Exist per layer. this is to optimize out empty maps in rendering.
+    pub filled_tiles: Vec<u32>,
     /// The size of the Tile to render. for spacing tiles out upon
     /// vertex creation. Default will be 20.
     pub tilesize: u32,
@@ -118,6 +187,10 @@ pub struct Map {
     pub camera_type: CameraType,
     /// If the position or a tile gets changed.
     pub changed: bool,
+    /// Animations tiles can play using [`TileData::animation`].
+    pub animations: Vec<TileAnimation>,
+    /// Width, Height and Layers of the map.
+    layout: MapLayout,
 }
 
 impl Map {
@@ -128,28 +201,33 @@ impl Map {
         renderer: &mut GpuRenderer,
         atlas: &mut AtlasSet,
     ) {
-        let atlas_width = atlas.size().x / self.tilesize;
-
         self.lower_buffer.clear();
         self.upper_buffer.clear();
 
-        for layer in MapLayers::LAYERS {
-            let z = layer.indexed_layers();
+        let (width, height) = (self.layout.width, self.layout.height);
+        let layer_tiles = self.layout.layer_tile_count();
+
+        for (layer_id, layer) in self.layout.layers.iter().enumerate() {
+            let z = layer.z;
 
-            if self.filled_tiles[layer as usize] == 0 {
+            if self.filled_tiles[layer_id] == 0 {
                 continue;
             }
 
-            for x in 0..32 {
-                for y in 0..32 {
-                    let tile = &self.tiles
-                        [(x + (y * 32) + (layer as u32 * 1024)) as usize];
+            for x in 0..width {
+                for y in 0..height {
+                    let tile = &self.tiles[x as usize
+                        + (y * width) as usize
+                        + layer_id * layer_tiles];
 
-                    if tile.id == 0 {
-                        continue;
-                    }
+                    // Animated tiles get one vertex per frame and the
+                    // shader only shows the current one.
+                    let (frames, time) = tile.frames(&self.animations);
 
-                    if let Some((allocation, _)) = atlas.peek(tile.id) {
+                    for (frame, id) in frames.iter().enumerate() {
+                        let Some((allocation, _)) = atlas.peek(*id) else {
+                            continue;
+                        };
                         let (posx, posy) = allocation.position();
 
                         let map_vertex = MapVertex {
@@ -159,17 +237,22 @@ impl Map {
                                 z,
                             ],
                             tilesize: self.tilesize as f32,
-                            tile_id: (posx / self.tilesize)
-                                + ((posy / self.tilesize) * atlas_width),
+                            tile_pos: [posx, posy],
                             texture_layer: allocation.layer as u32,
                             color: tile.color.0,
                             camera_type: self.camera_type as u32,
+                            frame: frame as u32,
+                            frames: frames.len() as u32,
+                            time,
+                            layer_frame: layer
+                                .anim_frame
+                                .map_or(0, |frame| frame + 1),
                         };
 
-                        if layer < MapLayers::Fringe {
-                            self.lower_buffer.push(map_vertex)
-                        } else {
+                        if layer.upper {
                             self.upper_buffer.push(map_vertex)
+                        } else {
+                            self.lower_buffer.push(map_vertex)
                         }
                     }
                 }
@@ -199,38 +282,74 @@ impl Map {
         }
     }
 
-    /// Creates a new [`Map`] with tilesize.
+    /// Creates a new [`Map`] with tilesize using the default [`MapLayout`]
+    /// of 32x32 tiles and the nine [`MapLayers`].
     ///
     pub fn new(renderer: &mut GpuRenderer, tilesize: u32) -> Self {
+        Self::with_layout(renderer, tilesize, MapLayout::default())
+    }
+
+    /// Creates a new [`Map`] with tilesize and a [`MapLayout`] setting its
+    /// Width, Height and Layers.
+    ///
+    /// The lower and upper layers are ordered by the lowest Z of their
+    /// layers. See [`MapLayout::order_z`].
+    ///
+    pub fn with_layout(
+        renderer: &mut GpuRenderer,
+        tilesize: u32,
+        layout: MapLayout,
+    ) -> Self {
         let map_vertex_size = bytemuck::bytes_of(&MapVertex::default()).len();
+        let (lower_count, upper_count) =
+            (layout.lower_count(), layout.upper_count());
 
-        let lower_index = renderer.new_buffer(map_vertex_size * LOWER_COUNT, 0);
-        let upper_index = renderer.new_buffer(map_vertex_size * UPPER_COUNT, 0);
+        let lower_index = renderer.new_buffer(map_vertex_size * lower_count, 0);
+        let upper_index = renderer.new_buffer(map_vertex_size * upper_count, 0);
 
-        let order1 = DrawOrder::new(false, Vec3::new(0.0, 0.0, 9.0), 0);
+        let order1 = DrawOrder::new(
+            false,
+            Vec3::new(0.0, 0.0, layout.order_z(false)),
+            0,
+        );
 
-        let order2 = DrawOrder::new(false, Vec3::new(0.0, 0.0, 5.0), 1);
+        let order2 =
+            DrawOrder::new(false, Vec3::new(0.0, 0.0, layout.order_z(true)), 1);
 
         Self {
-            tiles: iter::repeat_n(TileData::default(), 9216).collect(),
+            tiles: iter::repeat_n(TileData::default(), layout.tile_count())
+                .collect(),
             pos: Vec2::default(),
             stores: [lower_index, upper_index],
-            filled_tiles: [0; MapLayers::Count as usize],
-            lower_buffer: Vec::with_capacity(LOWER_COUNT),
-            upper_buffer: Vec::with_capacity(UPPER_COUNT),
+            filled_tiles: vec![0; layout.layer_count()],
+            lower_buffer: Vec::with_capacity(lower_count),
+            upper_buffer: Vec::with_capacity(upper_count),
             orders: [order1, order2],
             tilesize,
             can_render: false,
             changed: true,
             camera_type: CameraType::None,
+            animations: Vec::new(),
+            layout,
         }
     }
 
+    /// Returns the [`MapLayout`] of the [`Map`].
+    ///
+    pub fn layout(&self) -> &MapLayout {
+        &self.layout
+    }
+
+    /// Returns the Width and Height in tiles.
+    ///
+    pub fn size(&self) -> (u32, u32) {
+        (self.layout.width, self.layout.height)
+    }
+
     /// Updates the [`Map`]'s position.
     ///
     pub fn set_position(&mut self, position: Vec2) -> &mut Self {
-        self.orders[0].set_position(Vec3::new(position.x, position.y, 9.0));
-        self.orders[1].set_position(Vec3::new(position.x, position.y, 5.0));
+        self.set_order_pos(position);
         self.pos = position;
         self.changed = true;
         self
@@ -243,12 +362,12 @@ impl Map {
         self.orders[0].set_position(Vec3::new(
             order_override.x,
             order_override.y,
-            9.0,
+            self.layout.order_z(false),
         ));
         self.orders[1].set_position(Vec3::new(
             order_override.x,
             order_override.y,
-            5.0,
+            self.layout.order_z(true),
         ));
 
         self
@@ -277,16 +396,24 @@ impl Map {
         }
     }
 
-    /// gets the [`TileData`] based upon the tiles x, y, and [`MapLayers`].
-    /// [`MapLayers::Ground`] is Layer 0.
+    /// gets the [`TileData`] based upon the tiles x, y, and layer index within
+    /// the [`MapLayout`]. With the default layout [`MapLayers::Ground`] is Layer 0.
+    ///
+    /// # Panics
+    /// If the position is outside of the [`MapLayout`].
     ///
     pub fn get_tile(&self, pos: (u32, u32, u32)) -> TileData {
-        assert!(
-            pos.0 < 32 || pos.1 < 32 || pos.2 < 9,
-            "pos is invalid. X < 32, y < 256, z < 9"
-        );
-
-        self.tiles[(pos.0 + (pos.1 * 32) + (pos.2 * 1024)) as usize]
+        let index =
+            self.layout.index(pos.0, pos.1, pos.2).unwrap_or_else(|| {
+                panic!(
+                    "pos is invalid. x < {}, y < {}, z < {}",
+                    self.layout.width,
+                    self.layout.height,
+                    self.layout.layer_count()
+                )
+            });
+
+        self.tiles[index]
     }
 
     /// Sets the [`CameraType`] this object will use to Render with.
@@ -302,21 +429,16 @@ impl Map {
     /// This allows us to loop through the tiles Shader side efficiently.
     ///
     pub fn set_tile(&mut self, pos: (u32, u32, u32), tile: TileData) {
-        if pos.0 >= 32 || pos.1 >= 32 || pos.2 >= 9 {
+        let Some(tilepos) = self.layout.index(pos.0, pos.1, pos.2) else {
             return;
-        }
-        let tilepos = (pos.0 + (pos.1 * 32) + (pos.2 * 1024)) as usize;
-        let current_tile = self.tiles[tilepos];
+        };
+        let current_filled = self.tiles[tilepos].is_filled(&self.animations);
+        let filled = tile.is_filled(&self.animations);
 
-        if (current_tile.id > 0 && current_tile.color.a() > 0)
-            && (tile.color.a() == 0 || tile.id == 0)
-        {
+        if current_filled && !filled {
             self.filled_tiles[pos.2 as usize] =
                 self.filled_tiles[pos.2 as usize].saturating_sub(1);
-        } else if tile.color.a() > 0
-            && tile.id > 0
-            && (current_tile.id == 0 || current_tile.color.a() == 0)
-        {
+        } else if filled && !current_filled {
             self.filled_tiles[pos.2 as usize] =
                 self.filled_tiles[pos.2 as usize].saturating_add(1);
         }
@@ -325,6 +447,51 @@ impl Map {
         self.changed = true;
     }
 
+    /// Adds a [`TileAnimation`] tiles can play by setting
+    /// [`TileData::animation`] to the returned index.
+    ///
+    pub fn add_animation(&mut self, animation: TileAnimation) -> usize {
+        self.animations.push(animation);
+        self.count_filled();
+        self.changed = true;
+        self.animations.len() - 1
+    }
+
+    /// Replaces all [`TileAnimation`]'s keeping their indexes as given.
+    ///
+    pub fn set_animations(
+        &mut self,
+        animations: Vec<TileAnimation>,
+    ) -> &mut Self {
+        self.animations = animations;
+        self.count_filled();
+        self.changed = true;
+        self
+    }
+
+    /// Counts the filled tiles of each layer again as tiles playing an
+    /// animation may have started or stopped showing anything.
+    ///
+    fn count_filled(&mut self) {
+        let layer_tiles = self.layout.layer_tile_count();
+
+        for (layer, filled) in self.filled_tiles.iter_mut().enumerate() {
+            *filled = self.tiles[layer * layer_tiles..][..layer_tiles]
+                .iter()
+                .filter(|tile| tile.is_filled(&self.animations))
+                .count() as u32;
+        }
+    }
+
+    /// Resets every tile to [`TileData::default`] so the [`Map`] can be
+    /// reused while keeping its buffer stores.
+    ///
+    pub fn clear_tiles(&mut self) {
+        self.tiles.fill(TileData::default());
+        self.filled_tiles.fill(0);
+        self.changed = true;
+    }
+
     /// Used to check and update the [`Map`]'s Buffer for Rendering.
     /// Returns an Optional vec![Lower, Upper] [`OrderedIndex`] to use in Rendering.
     ///
@@ -0,0 +1,392 @@
+use crate::{AHashMap, Map, TileData, TileSheet, TileWorld};
+
+/// Neighbour bits of an autotile mask. North is up, towards higher Y.
+///
+pub const AUTOTILE_N: u8 = 1;
+pub const AUTOTILE_NE: u8 = 2;
+pub const AUTOTILE_E: u8 = 4;
+pub const AUTOTILE_SE: u8 = 8;
+pub const AUTOTILE_S: u8 = 16;
+pub const AUTOTILE_SW: u8 = 32;
+pub const AUTOTILE_W: u8 = 64;
+pub const AUTOTILE_NW: u8 = 128;
+
+/// Offsets of the neighbours in the order of their mask bits.
+///
+const NEIGHBOURS: [(i32, i32); 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+];
+
+/// How an [`AutotileSet`] picks its tile from the neighbours.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AutotileMode {
+    /// 16 tiles picked by the 4 side neighbours, also known as Wang edge
+    /// tiles. Index bits are N = 1, E = 2, S = 4 and W = 8.
+    Edge4,
+    /// 16 tiles picked by the 4 corners, also known as Wang corner tiles.
+    /// A corner counts when both of its sides and the corner itself are the
+    /// same terrain. Index bits are NE = 1, SE = 2, SW = 4 and NW = 8.
+    Corner4,
+    /// 47 tiles picked by all 8 neighbours, also known as blob tiles.
+    /// Corners only count when both of their sides do. Tiles are ordered by
+    /// their reduced mask from lowest to highest.
+    Blob8,
+}
+
+impl AutotileMode {
+    /// Returns the amount of tiles a set of this mode needs.
+    ///
+    pub fn tile_count(self) -> usize {
+        match self {
+            Self::Edge4 | Self::Corner4 => 16,
+            Self::Blob8 => 47,
+        }
+    }
+
+    /// Returns the index of the tile to use for the 8 neighbour mask.
+    ///
+    pub fn index(self, mask: u8) -> usize {
+        let has = |bit: u8| mask & bit != 0;
+
+        match self {
+            Self::Edge4 => {
+                has(AUTOTILE_N) as usize
+                    | (has(AUTOTILE_E) as usize) << 1
+                    | (has(AUTOTILE_S) as usize) << 2
+                    | (has(AUTOTILE_W) as usize) << 3
+            }
+            Self::Corner4 => {
+                let reduced = reduce_mask(mask);
+
+                (reduced & AUTOTILE_NE != 0) as usize
+                    | ((reduced & AUTOTILE_SE != 0) as usize) << 1
+                    | ((reduced & AUTOTILE_SW != 0) as usize) << 2
+                    | ((reduced & AUTOTILE_NW != 0) as usize) << 3
+            }
+            Self::Blob8 => {
+                let reduced = reduce_mask(mask);
+
+                (0..reduced).filter(|m| reduce_mask(*m) == *m).count()
+            }
+        }
+    }
+}
+
+/// Removes corners whose sides are not both set.
+///
+fn reduce_mask(mask: u8) -> u8 {
+    let mut reduced = mask;
+
+    for (corner, sides) in [
+        (AUTOTILE_NE, AUTOTILE_N | AUTOTILE_E),
+        (AUTOTILE_SE, AUTOTILE_S | AUTOTILE_E),
+        (AUTOTILE_SW, AUTOTILE_S | AUTOTILE_W),
+        (AUTOTILE_NW, AUTOTILE_N | AUTOTILE_W),
+    ] {
+        if mask & sides != sides {
+            reduced &= !corner;
+        }
+    }
+
+    reduced
+}
+
+/// Tiles of one terrain, ordered by the index [`AutotileMode::index`]
+/// gives for each neighbour mask.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct AutotileSet {
+    pub mode: AutotileMode,
+    /// Tile allocation IDs within the texture.
+    pub tiles: Vec<usize>,
+}
+
+impl AutotileSet {
+    /// Creates a new [`AutotileSet`].
+    /// Returns None if tiles does not hold [`AutotileMode::tile_count`] tiles.
+    ///
+    pub fn new(mode: AutotileMode, tiles: Vec<usize>) -> Option<Self> {
+        (tiles.len() == mode.tile_count()).then_some(Self { mode, tiles })
+    }
+
+    /// Creates a new [`AutotileSet`] from the tiles of a [`TileSheet`]
+    /// starting at first. Returns None if the [`TileSheet`] runs out of
+    /// tiles.
+    ///
+    pub fn from_tilesheet(
+        mode: AutotileMode,
+        sheet: &TileSheet,
+        first: usize,
+    ) -> Option<Self> {
+        let tiles = sheet.tiles.get(first..first + mode.tile_count())?;
+
+        Self::new(mode, tiles.iter().map(|tile| tile.tex_id).collect())
+    }
+
+    /// Returns the tile to use for the 8 neighbour mask.
+    ///
+    pub fn tile(&self, mask: u8) -> usize {
+        self.tiles[self.mode.index(mask)]
+    }
+}
+
+/// Tiles an [`AutotileRules`] can read and paint, addressed by X, Y and
+/// layer. Returns None for positions that do not exist or are not loaded.
+///
+pub trait AutotileTarget {
+    fn autotile_get(&self, pos: (i32, i32, u32)) -> Option<TileData>;
+
+    fn autotile_set(&mut self, pos: (i32, i32, u32), tile: TileData);
+}
+
+impl AutotileTarget for Map {
+    fn autotile_get(&self, pos: (i32, i32, u32)) -> Option<TileData> {
+        let (x, y) = (u32::try_from(pos.0).ok()?, u32::try_from(pos.1).ok()?);
+
+        self.layout()
+            .index(x, y, pos.2)
+            .map(|index| self.tiles[index])
+    }
+
+    fn autotile_set(&mut self, pos: (i32, i32, u32), tile: TileData) {
+        if pos.0 >= 0 && pos.1 >= 0 {
+            self.set_tile((pos.0 as u32, pos.1 as u32, pos.2), tile);
+        }
+    }
+}
+
+/// Positions are world tile positions, so painting updates neighbours
+/// across the borders of the chunks.
+///
+impl AutotileTarget for TileWorld {
+    fn autotile_get(&self, pos: (i32, i32, u32)) -> Option<TileData> {
+        self.get_tile(pos)
+    }
+
+    fn autotile_set(&mut self, pos: (i32, i32, u32), tile: TileData) {
+        self.set_tile(pos, tile);
+    }
+}
+
+/// Terrain rules that pick each tile from its neighbours so terrain can
+/// be painted without placing every edge and corner by hand.
+///
+/// The terrain of a tile is found from its ID, so every tile must belong
+/// to a single [`AutotileSet`]. Only neighbours on the same layer count.
+///
+/// # Suggested usage
+/// Add an [`AutotileSet`] per terrain, then use [`AutotileRules::paint`]
+/// and [`AutotileRules::erase`] instead of [`Map::set_tile`] on a [`Map`]
+/// or a [`TileWorld`].
+///
+#[derive(Clone, Debug)]
+pub struct AutotileRules {
+    /// Sets by their terrain ID.
+    sets: Vec<AutotileSet>,
+    /// Terrain ID of each tile allocation ID.
+    lookup: AHashMap<usize, usize>,
+    /// If positions outside of the target count as the same terrain.
+    edge_connects: bool,
+}
+
+impl Default for AutotileRules {
+    fn default() -> Self {
+        Self {
+            sets: Vec::new(),
+            lookup: AHashMap::default(),
+            edge_connects: true,
+        }
+    }
+}
+
+impl AutotileRules {
+    /// Creates new empty [`AutotileRules`].
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets if positions outside of the [`Map`] or in unloaded chunks count
+    /// as the same terrain so it runs off the edge when building the
+    /// [`AutotileRules`]. Default is true.
+    ///
+    pub fn with_edge_connects(mut self, edge_connects: bool) -> Self {
+        self.edge_connects = edge_connects;
+        self
+    }
+
+    /// Sets if positions outside of the [`Map`] or in unloaded chunks count
+    /// as the same terrain.
+    ///
+    pub fn set_edge_connects(&mut self, edge_connects: bool) -> &mut Self {
+        self.edge_connects = edge_connects;
+        self
+    }
+
+    /// Adds an [`AutotileSet`] and returns its terrain ID.
+    ///
+    pub fn add_set(&mut self, set: AutotileSet) -> usize {
+        let terrain = self.sets.len();
+
+        for id in set.tiles.iter().filter(|id| **id > 0) {
+            self.lookup.entry(*id).or_insert(terrain);
+        }
+
+        self.sets.push(set);
+        terrain
+    }
+
+    /// Returns the [`AutotileSet`] of a terrain.
+    ///
+    pub fn set(&self, terrain: usize) -> Option<&AutotileSet> {
+        self.sets.get(terrain)
+    }
+
+    /// Returns the terrain of a tile allocation ID.
+    ///
+    pub fn terrain_of(&self, id: usize) -> Option<usize> {
+        self.lookup.get(&id).copied()
+    }
+
+    /// Returns the terrain at a position.
+    ///
+    pub fn terrain_at(
+        &self,
+        target: &impl AutotileTarget,
+        pos: (i32, i32, u32),
+    ) -> Option<usize> {
+        target
+            .autotile_get(pos)
+            .and_then(|tile| self.terrain_of(tile.id))
+    }
+
+    /// Returns the 8 neighbour mask of the neighbours that are terrain.
+    ///
+    pub fn mask(
+        &self,
+        target: &impl AutotileTarget,
+        pos: (i32, i32, u32),
+        terrain: usize,
+    ) -> u8 {
+        NEIGHBOURS
+            .iter()
+            .enumerate()
+            .filter(|(_, (x, y))| {
+                let neighbour = (pos.0 + x, pos.1 + y, pos.2);
+
+                match target.autotile_get(neighbour) {
+                    Some(tile) => self.terrain_of(tile.id) == Some(terrain),
+                    None => self.edge_connects,
+                }
+            })
+            .fold(0, |mask, (bit, _)| mask | (1 << bit))
+    }
+
+    /// Paints terrain at a position and updates the neighbouring tiles.
+    /// Returns false if the terrain does not exist or the position can not
+    /// be set.
+    ///
+    pub fn paint(
+        &self,
+        target: &mut impl AutotileTarget,
+        pos: (i32, i32, u32),
+        terrain: usize,
+    ) -> bool {
+        let (Some(set), Some(tile)) =
+            (self.sets.get(terrain), target.autotile_get(pos))
+        else {
+            return false;
+        };
+
+        // Placed first so the neighbours see it, then picked for real.
+        target.autotile_set(
+            pos,
+            TileData {
+                id: set.tile(0),
+                animation: None,
+                ..tile
+            },
+        );
+        self.refresh(target, pos);
+        self.refresh_neighbours(target, pos);
+        true
+    }
+
+    /// Clears the tile at a position and updates the neighbouring tiles.
+    /// Returns false if the position can not be set.
+    ///
+    pub fn erase(
+        &self,
+        target: &mut impl AutotileTarget,
+        pos: (i32, i32, u32),
+    ) -> bool {
+        let Some(tile) = target.autotile_get(pos) else {
+            return false;
+        };
+
+        target.autotile_set(
+            pos,
+            TileData {
+                id: 0,
+                animation: None,
+                ..tile
+            },
+        );
+        self.refresh_neighbours(target, pos);
+        true
+    }
+
+    /// Picks the tile at a position again if it is terrain.
+    ///
+    pub fn refresh(
+        &self,
+        target: &mut impl AutotileTarget,
+        pos: (i32, i32, u32),
+    ) {
+        let Some(tile) = target.autotile_get(pos) else {
+            return;
+        };
+        let Some(terrain) = self.terrain_of(tile.id) else {
+            return;
+        };
+
+        let id = self.sets[terrain].tile(self.mask(target, pos, terrain));
+
+        if id != tile.id {
+            target.autotile_set(pos, TileData { id, ..tile });
+        }
+    }
+
+    /// Picks the tiles around a position again.
+    ///
+    pub fn refresh_neighbours(
+        &self,
+        target: &mut impl AutotileTarget,
+        pos: (i32, i32, u32),
+    ) {
+        for (x, y) in NEIGHBOURS {
+            self.refresh(target, (pos.0 + x, pos.1 + y, pos.2));
+        }
+    }
+
+    /// Picks every terrain tile of a layer within a [`Map`] again, for
+    /// example after loading it.
+    ///
+    pub fn refresh_map(&self, map: &mut Map, layer: u32) {
+        let (width, height) = map.size();
+
+        for y in 0..height as i32 {
+            for x in 0..width as i32 {
+                self.refresh(map, (x, y, layer));
+            }
+        }
+    }
+}
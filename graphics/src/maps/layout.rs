@@ -0,0 +1,172 @@
+use crate::MapLayers;
+
+/// Settings of a single layer of a [`crate::Map`].
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapLayerSettings {
+    /// Name shown in editors.
+    pub name: String,
+    /// Z value the layer's tiles are rendered at.
+    pub z: f32,
+    /// If the layer is rendered above the player like the fringe layers.
+    pub upper: bool,
+    /// Frame of the four frame layer animation the layer is shown in,
+    /// switching every 250 milliseconds. Set for the Anim layers of the
+    /// default [`MapLayout`].
+    pub anim_frame: Option<u32>,
+}
+
+impl MapLayerSettings {
+    /// Creates a new [`MapLayerSettings`].
+    ///
+    pub fn new(name: impl Into<String>, z: f32, upper: bool) -> Self {
+        Self {
+            name: name.into(),
+            z,
+            upper,
+            anim_frame: None,
+        }
+    }
+
+    /// Shows the layer only in one frame of the four frame layer animation
+    /// when building the [`MapLayerSettings`].
+    ///
+    pub fn with_anim_frame(mut self, frame: u32) -> Self {
+        self.anim_frame = Some(frame % 4);
+        self
+    }
+}
+
+/// Width, Height and Layers of a [`crate::Map`].
+/// Defaults to 32x32 tiles with the nine [`MapLayers`].
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapLayout {
+    /// Width in tiles.
+    pub width: u32,
+    /// Height in tiles.
+    pub height: u32,
+    /// Layers from the bottom up.
+    pub layers: Vec<MapLayerSettings>,
+}
+
+impl Default for MapLayout {
+    fn default() -> Self {
+        Self {
+            width: 32,
+            height: 32,
+            layers: MapLayers::LAYERS
+                .iter()
+                .map(|&layer| {
+                    let settings = MapLayerSettings::new(
+                        layer.as_str(),
+                        layer.indexed_layers(),
+                        layer >= MapLayers::Fringe,
+                    );
+
+                    match layer {
+                        MapLayers::Anim1 => settings.with_anim_frame(0),
+                        MapLayers::Anim2 => settings.with_anim_frame(1),
+                        MapLayers::Anim3 => settings.with_anim_frame(2),
+                        MapLayers::Anim4 => settings.with_anim_frame(3),
+                        _ => settings,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl MapLayout {
+    /// Creates a new [`MapLayout`] of Width and Height in tiles without any
+    /// layers. Add them with [`MapLayout::with_layer`].
+    ///
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            layers: Vec::new(),
+        }
+    }
+
+    /// Adds a layer above the existing ones.
+    ///
+    pub fn push_layer(&mut self, layer: MapLayerSettings) -> &mut Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Adds a layer above the existing ones when building the [`MapLayout`].
+    ///
+    pub fn with_layer(
+        mut self,
+        name: impl Into<String>,
+        z: f32,
+        upper: bool,
+    ) -> Self {
+        self.layers.push(MapLayerSettings::new(name, z, upper));
+        self
+    }
+
+    /// Returns the amount of layers.
+    ///
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns the amount of tiles within a single layer.
+    ///
+    pub fn layer_tile_count(&self) -> usize {
+        self.width as usize * self.height as usize
+    }
+
+    /// Returns the amount of tiles over all layers.
+    ///
+    pub fn tile_count(&self) -> usize {
+        self.layer_tile_count() * self.layers.len()
+    }
+
+    /// Returns the amount of tiles over all lower layers.
+    ///
+    pub fn lower_count(&self) -> usize {
+        self.layer_tile_count()
+            * self.layers.iter().filter(|layer| !layer.upper).count()
+    }
+
+    /// Returns the amount of tiles over all upper layers.
+    ///
+    pub fn upper_count(&self) -> usize {
+        self.layer_tile_count()
+            * self.layers.iter().filter(|layer| layer.upper).count()
+    }
+
+    /// Returns the Z the lower or upper layers are ordered by, which is the
+    /// lowest Z of their layers.
+    ///
+    pub fn order_z(&self, upper: bool) -> f32 {
+        self.layers
+            .iter()
+            .filter(|layer| layer.upper == upper)
+            .map(|layer| layer.z)
+            .reduce(f32::min)
+            .unwrap_or(if upper { 5.0 } else { 9.0 })
+    }
+
+    /// Returns the index of the tile within [`crate::Map::tiles`].
+    /// None if the position is outside of the [`MapLayout`].
+    ///
+    pub fn index(&self, x: u32, y: u32, layer: u32) -> Option<usize> {
+        if x >= self.width
+            || y >= self.height
+            || layer as usize >= self.layers.len()
+        {
+            return None;
+        }
+
+        Some(
+            x as usize
+                + y as usize * self.width as usize
+                + layer as usize * self.layer_tile_count(),
+        )
+    }
+}
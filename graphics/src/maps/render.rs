@@ -1,6 +1,7 @@
 use crate::{
-    AsBufferPass, AtlasSet, GpuRenderer, GraphicsError, InstanceBuffer, Map,
-    MapRenderPipeline, MapVertex, OrderedIndex, SetBuffers, StaticVertexBuffer,
+    AsBufferPass, AtlasSet, BlendMode, GpuRenderer, GraphicsError,
+    InstanceBuffer, Map, MapRenderPipeline, MapVertex, OrderedIndex,
+    SetBuffers, StaticVertexBuffer, TileWorld,
 };
 use log::warn;
 
@@ -9,6 +10,8 @@ use log::warn;
 pub struct MapRenderer {
     /// Instance Buffer holding all Rendering information for [`Map`]'s.
     pub buffer: InstanceBuffer<MapVertex>,
+    /// [`BlendMode`] the Renderer draws with.
+    blend_mode: BlendMode,
 }
 
 impl MapRenderer {
@@ -27,9 +30,36 @@ impl MapRenderer {
                 9_216 * map_count as usize,
                 144,
             ),
+            blend_mode: BlendMode::Alpha,
         })
     }
 
+    /// Creates a new [`MapRenderer`] that draws using a [`BlendMode`].
+    /// Creates the [`MapRenderPipeline`] for the mode if it does not yet exist.
+    ///
+    /// # Arguments
+    /// - map_count: The number of Maps to presize the instance buffer by.
+    /// - blend_mode: [`BlendMode`] used to draw with.
+    ///
+    pub fn with_blend_mode(
+        renderer: &mut GpuRenderer,
+        map_count: u32,
+        blend_mode: BlendMode,
+    ) -> Result<Self, GraphicsError> {
+        renderer.create_pipeline(MapRenderPipeline::new(blend_mode));
+
+        let mut this = Self::new(renderer, map_count)?;
+
+        this.blend_mode = blend_mode;
+        Ok(this)
+    }
+
+    /// Returns the [`BlendMode`] the Renderer draws with.
+    ///
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
     /// Adds a Buffer [`OrderedIndex`] to the Rendering Store to get processed.
     /// This must be done before [`MapRenderer::finalize`] but after [`Map::update`] in order for it to Render.
     ///
@@ -75,6 +105,28 @@ impl MapRenderer {
         }
     }
 
+    /// Updates every loaded chunk of a [`TileWorld`] and adds their
+    /// [`OrderedIndex`]'s to staging like [`MapRenderer::map_update`].
+    ///
+    /// # Arguments
+    /// - world: [`TileWorld`] we want to update and prepare for rendering.
+    /// - atlas: [`AtlasSet`] the chunks need to render with.
+    /// - buffer_layers: The Buffer Layer's we want to add this Object too.
+    ///
+    pub fn world_update(
+        &mut self,
+        world: &mut TileWorld,
+        renderer: &mut GpuRenderer,
+        atlas: &mut AtlasSet,
+        buffer_layers: [usize; 2],
+    ) {
+        for orders in world.update(renderer, atlas) {
+            for (id, order_index) in orders.into_iter().enumerate() {
+                self.add_buffer_store(renderer, order_index, buffer_layers[id]);
+            }
+        }
+    }
+
     /// Map does not use Clipping.
     pub fn use_clipping(&mut self) {
         warn!("Map does not use Clipping.");
@@ -114,7 +166,11 @@ where
                 self.set_bind_group(1, atlas.bind_group(), &[]);
                 self.set_vertex_buffer(1, buffer.buffer.instances(None));
                 self.set_pipeline(
-                    renderer.get_pipelines(MapRenderPipeline).unwrap(),
+                    renderer
+                        .get_pipelines(MapRenderPipeline::new(
+                            buffer.blend_mode,
+                        ))
+                        .unwrap(),
                 );
                 self.draw_indexed(
                     0..StaticVertexBuffer::index_count(),
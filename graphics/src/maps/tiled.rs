@@ -0,0 +1,851 @@
+use crate::{
+    AtlasSet, GpuRenderer, GraphicsError, Map, MapLayers, MapLayout, Texture,
+    TileData, TileSheet, Vec2,
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use cosmic_text::Color;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use serde::Deserialize;
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Bits Tiled sets within a GID to flip or rotate the tile.
+///
+const FLIP_FLAGS: u32 = 0xF000_0000;
+
+/// [`Map`]'s made by [`TiledMap::create_maps`] with their X and Y counted in
+/// [`Map`]'s.
+///
+pub type TiledMaps = Vec<((u32, u32), Map)>;
+
+/// Tileset of a [`TiledMap`] made from a single image.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct TiledTileset {
+    /// GID of the first tile within this tileset.
+    pub first_gid: u32,
+    pub name: String,
+    pub tile_width: u32,
+    pub tile_height: u32,
+    pub tile_count: u32,
+    pub columns: u32,
+    pub margin: u32,
+    pub spacing: u32,
+    /// Path of the tileset image.
+    pub image: PathBuf,
+}
+
+/// Tile layer of a [`TiledMap`]. Layers within groups are flattened.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct TiledLayer {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub opacity: f32,
+    pub visible: bool,
+    /// GIDs from the top left, row by row, including Tiled's flip flags.
+    pub data: Vec<u32>,
+}
+
+/// Orthogonal map made with the Tiled editor, read from a `.tmx` or `.tmj`
+/// file. Layers may be CSV or base64 encoded with zlib or gzip compression
+/// and tilesets may be embedded or external.
+///
+/// Tiled's rows go from the top down while [`Map`] rows go from the bottom
+/// up, so rows are flipped on import. Flipped and rotated tiles are placed
+/// without their flip as [`Map`] can not show them.
+///
+/// # Suggested usage
+/// Use [`TiledMap::create_maps`] to split the map into [`Map`]'s, or load
+/// the tilesets with [`TiledMap::load_tilesets`] and call
+/// [`TiledMap::fill_map`] from a [`crate::ChunkSource`] to stream it within
+/// a [`crate::TileWorld`].
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct TiledMap {
+    /// Width in tiles.
+    pub width: u32,
+    /// Height in tiles.
+    pub height: u32,
+    pub tile_width: u32,
+    pub tile_height: u32,
+    pub tilesets: Vec<TiledTileset>,
+    pub layers: Vec<TiledLayer>,
+}
+
+/// Tiled GIDs remapped onto the [`AtlasSet`] IDs of their tiles.
+/// Created by [`TiledMap::load_tilesets`].
+///
+#[derive(Debug, Default)]
+pub struct TiledTiles {
+    /// [`TileSheet`] of each tileset by its first GID, sorted.
+    sheets: Vec<(u32, TileSheet)>,
+}
+
+impl TiledTiles {
+    /// Returns the tile's allocation ID within the [`AtlasSet`].
+    /// None for empty tiles and GIDs outside of the tilesets.
+    ///
+    pub fn atlas_id(&self, gid: u32) -> Option<usize> {
+        let gid = gid & !FLIP_FLAGS;
+
+        if gid == 0 {
+            return None;
+        }
+
+        let (first_gid, sheet) = self
+            .sheets
+            .iter()
+            .rev()
+            .find(|(first_gid, _)| *first_gid <= gid)?;
+
+        sheet
+            .tiles
+            .get((gid - first_gid) as usize)
+            .map(|tile| tile.tex_id)
+    }
+}
+
+/// Rules which [`Map`] layer a Tiled layer is placed on.
+///
+/// Tiled layers without a rule go onto the [`MapLayout`] layer of the same
+/// name ignoring case, like `Ground` or `Fringe 2` with the default
+/// layout. Layers matching neither are skipped.
+///
+#[derive(Clone, Debug, Default)]
+pub struct TiledLayerRules {
+    rules: Vec<(String, u32)>,
+}
+
+impl TiledLayerRules {
+    /// Creates new [`TiledLayerRules`] without any rules.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Places the Tiled layer on one of the default [`MapLayers`].
+    ///
+    pub fn with_rule(
+        self,
+        tiled_layer: impl Into<String>,
+        layer: MapLayers,
+    ) -> Self {
+        self.with_layer_index(tiled_layer, layer as u32)
+    }
+
+    /// Places the Tiled layer on the [`MapLayout`] layer of index.
+    ///
+    pub fn with_layer_index(
+        mut self,
+        tiled_layer: impl Into<String>,
+        layer: u32,
+    ) -> Self {
+        self.rules.push((tiled_layer.into(), layer));
+        self
+    }
+
+    /// Returns the index of the [`MapLayout`] layer the Tiled layer goes on.
+    ///
+    pub fn map_layer(
+        &self,
+        tiled_layer: &str,
+        layout: &MapLayout,
+    ) -> Option<u32> {
+        self.rules
+            .iter()
+            .find(|(name, _)| name == tiled_layer)
+            .map(|(_, layer)| *layer)
+            .or_else(|| {
+                layout
+                    .layers
+                    .iter()
+                    .position(|layer| {
+                        layer.name.eq_ignore_ascii_case(tiled_layer)
+                    })
+                    .map(|index| index as u32)
+            })
+            .filter(|layer| (*layer as usize) < layout.layer_count())
+    }
+}
+
+impl TiledMap {
+    /// Reads a `.tmx` or `.tmj` file. External tilesets and images are
+    /// loaded relative to the file that references them.
+    ///
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, GraphicsError> {
+        let path = path.as_ref();
+        let data = fs::read_to_string(path)?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let name = path.to_string_lossy();
+
+        if is_xml(path, &data) {
+            Self::from_tmx(&name, &data, dir)
+        } else {
+            Self::from_tmj(&name, &data, dir)
+        }
+    }
+
+    /// Reads a `.tmx` map. dir is where relative paths start from.
+    ///
+    pub fn from_tmx(
+        name: &str,
+        data: &str,
+        dir: &Path,
+    ) -> Result<Self, GraphicsError> {
+        let doc = roxmltree::Document::parse(data)?;
+        let root = doc.root_element();
+
+        if !root.has_tag_name("map") {
+            return Err(invalid(name, "the root element is not a map"));
+        }
+
+        check_map(
+            name,
+            root.attribute("orientation").unwrap_or("orthogonal"),
+            root.attribute("infinite") == Some("1"),
+        )?;
+
+        let mut map = Self {
+            width: required(name, &root, "width")?,
+            height: required(name, &root, "height")?,
+            tile_width: required(name, &root, "tilewidth")?,
+            tile_height: required(name, &root, "tileheight")?,
+            tilesets: Vec::new(),
+            layers: Vec::new(),
+        };
+
+        for tileset in root.children().filter(|n| n.has_tag_name("tileset")) {
+            let first_gid = required(name, &tileset, "firstgid")?;
+
+            map.tilesets.push(match tileset.attribute("source") {
+                Some(source) => external_tileset(&dir.join(source), first_gid)?,
+                None => xml_tileset(name, &tileset, first_gid, dir)?,
+            });
+        }
+
+        xml_layers(name, &root, 1.0, true, &mut map.layers)?;
+        map.tilesets.sort_by_key(|tileset| tileset.first_gid);
+        Ok(map)
+    }
+
+    /// Reads a `.tmj` map. dir is where relative paths start from.
+    ///
+    pub fn from_tmj(
+        name: &str,
+        data: &str,
+        dir: &Path,
+    ) -> Result<Self, GraphicsError> {
+        let json: JsonMap = serde_json::from_str(data)?;
+
+        check_map(name, &json.orientation, json.infinite)?;
+
+        let mut map = Self {
+            width: json.width,
+            height: json.height,
+            tile_width: json.tilewidth,
+            tile_height: json.tileheight,
+            tilesets: Vec::with_capacity(json.tilesets.len()),
+            layers: Vec::new(),
+        };
+
+        for tileset in json.tilesets {
+            let first_gid = tileset.firstgid;
+
+            map.tilesets.push(match &tileset.source {
+                Some(source) => external_tileset(&dir.join(source), first_gid)?,
+                None => tileset.into_tileset(name, first_gid, dir)?,
+            });
+        }
+
+        json_layers(name, json.layers, 1.0, true, &mut map.layers)?;
+        map.tilesets.sort_by_key(|tileset| tileset.first_gid);
+        Ok(map)
+    }
+
+    /// Loads every tileset image through [`TileSheet::new`] into the
+    /// [`AtlasSet`] and returns the lookup from GIDs to atlas IDs.
+    ///
+    /// Tiles must be square and the size of the map's tiles, and tilesets
+    /// can not use a margin or spacing.
+    ///
+    pub fn load_tilesets(
+        &self,
+        renderer: &GpuRenderer,
+        atlas: &mut AtlasSet<String, i32>,
+    ) -> Result<TiledTiles, GraphicsError> {
+        let mut tiles = TiledTiles::default();
+
+        for tileset in &self.tilesets {
+            if tileset.tile_width != self.tile_width
+                || tileset.tile_height != self.tile_width
+                || self.tile_height != self.tile_width
+            {
+                return Err(invalid(
+                    &tileset.name,
+                    "tiles must be square and match the map's tile size",
+                ));
+            }
+
+            if tileset.margin != 0 || tileset.spacing != 0 {
+                return Err(invalid(
+                    &tileset.name,
+                    "tilesets with a margin or spacing are not supported",
+                ));
+            }
+
+            let texture = Texture::from_file(&tileset.image)?;
+
+            if texture.is_compressed() {
+                return Err(GraphicsError::UnsupportedTextureFile(
+                    tileset.image.display().to_string(),
+                    "compressed textures can not be split into tiles".into(),
+                ));
+            }
+
+            if !texture.fits_format(atlas.format) {
+                return Err(GraphicsError::UnsupportedTextureFile(
+                    tileset.image.display().to_string(),
+                    format!(
+                        "{:?} does not fit the AtlasSet's {:?}",
+                        texture.format(),
+                        atlas.format
+                    ),
+                ));
+            }
+
+            let sheet =
+                TileSheet::new(texture, renderer, atlas, self.tile_width)
+                    .ok_or(GraphicsError::AtlasFull)?;
+
+            tiles.sheets.push((tileset.first_gid, sheet));
+        }
+
+        Ok(tiles)
+    }
+
+    /// Sets the tiles of a [`Map`] from the region of this map starting at
+    /// origin in tiles, counted from the bottom left. Tiles outside of this
+    /// map are left as they are, as are hidden layers and layers
+    /// [`TiledLayerRules`] do not place.
+    ///
+    /// The layer opacity is used as the tiles' alpha.
+    ///
+    pub fn fill_map(
+        &self,
+        map: &mut Map,
+        tiles: &TiledTiles,
+        rules: &TiledLayerRules,
+        origin: (i32, i32),
+    ) {
+        let (width, height) = map.size();
+
+        for layer in self.layers.iter().filter(|layer| layer.visible) {
+            let Some(map_layer) = rules.map_layer(&layer.name, map.layout())
+            else {
+                continue;
+            };
+            let alpha = (layer.opacity.clamp(0.0, 1.0) * 255.0).round() as u8;
+            let color = Color::rgba(255, 255, 255, alpha);
+
+            for y in 0..height {
+                for x in 0..width {
+                    let (tile_x, tile_y) =
+                        (origin.0 + x as i32, origin.1 + y as i32);
+
+                    if tile_x < 0
+                        || tile_y < 0
+                        || tile_x >= layer.width as i32
+                        || tile_y >= layer.height as i32
+                    {
+                        continue;
+                    }
+
+                    let row = layer.height - 1 - tile_y as u32;
+                    let gid = layer.data
+                        [row as usize * layer.width as usize + tile_x as usize];
+
+                    if let Some(id) = tiles.atlas_id(gid) {
+                        map.set_tile(
+                            (x, y, map_layer),
+                            TileData {
+                                id,
+                                color,
+                                ..Default::default()
+                            },
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Loads the tilesets and splits this map into as many [`Map`]'s of
+    /// [`MapLayout`] as needed to cover it. Returns each [`Map`] with its
+    /// X and Y counted in [`Map`]'s from the bottom left, already positioned
+    /// next to each other and ready to render.
+    ///
+    pub fn create_maps(
+        &self,
+        renderer: &mut GpuRenderer,
+        atlas: &mut AtlasSet<String, i32>,
+        rules: &TiledLayerRules,
+        layout: MapLayout,
+    ) -> Result<TiledMaps, GraphicsError> {
+        let tiles = self.load_tilesets(renderer, atlas)?;
+        let (columns, rows) = (
+            self.width.div_ceil(layout.width.max(1)),
+            self.height.div_ceil(layout.height.max(1)),
+        );
+        let mut maps = Vec::with_capacity((columns * rows) as usize);
+
+        for y in 0..rows {
+            for x in 0..columns {
+                let origin = (x * layout.width, y * layout.height);
+                let mut map =
+                    Map::with_layout(renderer, self.tile_width, layout.clone());
+
+                map.set_position(Vec2::new(
+                    (origin.0 * self.tile_width) as f32,
+                    (origin.1 * self.tile_width) as f32,
+                ));
+                self.fill_map(
+                    &mut map,
+                    &tiles,
+                    rules,
+                    (origin.0 as i32, origin.1 as i32),
+                );
+                map.can_render = true;
+                maps.push(((x, y), map));
+            }
+        }
+
+        Ok(maps)
+    }
+}
+
+/// Tiled map as stored within a `.tmj` file.
+///
+#[derive(Deserialize)]
+struct JsonMap {
+    #[serde(default)]
+    orientation: String,
+    width: u32,
+    height: u32,
+    tilewidth: u32,
+    tileheight: u32,
+    #[serde(default)]
+    infinite: bool,
+    #[serde(default)]
+    tilesets: Vec<JsonTileset>,
+    #[serde(default)]
+    layers: Vec<JsonLayer>,
+}
+
+/// Tileset as stored within a `.tmj` or `.tsj` file.
+///
+#[derive(Deserialize)]
+struct JsonTileset {
+    #[serde(default)]
+    firstgid: u32,
+    source: Option<String>,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    tilewidth: u32,
+    #[serde(default)]
+    tileheight: u32,
+    #[serde(default)]
+    tilecount: u32,
+    #[serde(default)]
+    columns: u32,
+    #[serde(default)]
+    margin: u32,
+    #[serde(default)]
+    spacing: u32,
+    image: Option<String>,
+}
+
+impl JsonTileset {
+    fn into_tileset(
+        self,
+        map_name: &str,
+        first_gid: u32,
+        dir: &Path,
+    ) -> Result<TiledTileset, GraphicsError> {
+        let image = self.image.ok_or_else(|| {
+            invalid(map_name, "image collection tilesets are not supported")
+        })?;
+
+        Ok(TiledTileset {
+            first_gid,
+            name: self.name,
+            tile_width: self.tilewidth,
+            tile_height: self.tileheight,
+            tile_count: self.tilecount,
+            columns: self.columns,
+            margin: self.margin,
+            spacing: self.spacing,
+            image: dir.join(image),
+        })
+    }
+}
+
+/// Layer as stored within a `.tmj` file.
+///
+#[derive(Deserialize)]
+struct JsonLayer {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    width: u32,
+    #[serde(default)]
+    height: u32,
+    #[serde(default = "default_opacity")]
+    opacity: f32,
+    #[serde(default = "default_visible")]
+    visible: bool,
+    data: Option<JsonData>,
+    encoding: Option<String>,
+    compression: Option<String>,
+    #[serde(default)]
+    layers: Vec<JsonLayer>,
+}
+
+/// Layer data which is an array of GIDs or an encoded string.
+///
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonData {
+    Gids(Vec<u32>),
+    Encoded(String),
+}
+
+fn default_opacity() -> f32 {
+    1.0
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+/// Creates a [`GraphicsError::InvalidTiledMap`].
+///
+fn invalid(name: &str, reason: &str) -> GraphicsError {
+    GraphicsError::InvalidTiledMap(name.to_owned(), reason.to_owned())
+}
+
+/// If the file is XML like `.tmx` and `.tsx` rather than JSON.
+///
+fn is_xml(path: &Path, data: &str) -> bool {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("tmx") => true,
+        Some(ext) if ext.eq_ignore_ascii_case("tsx") => true,
+        Some(ext) if ext.eq_ignore_ascii_case("xml") => true,
+        _ => data.trim_start().starts_with('<'),
+    }
+}
+
+/// Errors on maps that can not be placed onto a [`Map`].
+///
+fn check_map(
+    name: &str,
+    orientation: &str,
+    infinite: bool,
+) -> Result<(), GraphicsError> {
+    if orientation != "orthogonal" {
+        return Err(invalid(
+            name,
+            &format!("{orientation} maps are not supported"),
+        ));
+    }
+
+    if infinite {
+        return Err(invalid(name, "infinite maps are not supported"));
+    }
+
+    Ok(())
+}
+
+/// Reads an attribute that must exist.
+///
+fn required<T: FromStr>(
+    name: &str,
+    node: &roxmltree::Node,
+    attribute: &str,
+) -> Result<T, GraphicsError> {
+    node.attribute(attribute)
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| {
+            invalid(
+                name,
+                &format!(
+                    "{} is missing a valid {attribute}",
+                    node.tag_name().name()
+                ),
+            )
+        })
+}
+
+/// Reads an attribute or returns default if it does not exist.
+///
+fn optional<T: FromStr>(
+    node: &roxmltree::Node,
+    attribute: &str,
+    default: T,
+) -> T {
+    node.attribute(attribute)
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Reads a `.tsx` or `.tsj` tileset file.
+///
+fn external_tileset(
+    path: &Path,
+    first_gid: u32,
+) -> Result<TiledTileset, GraphicsError> {
+    let data = fs::read_to_string(path)?;
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let name = path.to_string_lossy();
+
+    if is_xml(path, &data) {
+        let doc = roxmltree::Document::parse(&data)?;
+
+        xml_tileset(&name, &doc.root_element(), first_gid, dir)
+    } else {
+        serde_json::from_str::<JsonTileset>(&data)?
+            .into_tileset(&name, first_gid, dir)
+    }
+}
+
+/// Reads a tileset element of a `.tmx` or `.tsx` file.
+///
+fn xml_tileset(
+    name: &str,
+    node: &roxmltree::Node,
+    first_gid: u32,
+    dir: &Path,
+) -> Result<TiledTileset, GraphicsError> {
+    let image = node
+        .children()
+        .find(|child| child.has_tag_name("image"))
+        .and_then(|image| image.attribute("source"))
+        .ok_or_else(|| {
+            invalid(name, "image collection tilesets are not supported")
+        })?;
+
+    Ok(TiledTileset {
+        first_gid,
+        name: node.attribute("name").unwrap_or_default().to_owned(),
+        tile_width: required(name, node, "tilewidth")?,
+        tile_height: required(name, node, "tileheight")?,
+        tile_count: optional(node, "tilecount", 0),
+        columns: optional(node, "columns", 0),
+        margin: optional(node, "margin", 0),
+        spacing: optional(node, "spacing", 0),
+        image: dir.join(image),
+    })
+}
+
+/// Reads the tile layers of a map or group element, flattening groups.
+///
+fn xml_layers(
+    name: &str,
+    node: &roxmltree::Node,
+    opacity: f32,
+    visible: bool,
+    layers: &mut Vec<TiledLayer>,
+) -> Result<(), GraphicsError> {
+    for child in node.children().filter(|child| child.is_element()) {
+        let child_opacity = opacity * optional(&child, "opacity", 1.0);
+        let child_visible = visible && optional(&child, "visible", 1u8) != 0;
+
+        match child.tag_name().name() {
+            "group" => {
+                xml_layers(name, &child, child_opacity, child_visible, layers)?
+            }
+            "layer" => {
+                let (width, height) = (
+                    required(name, &child, "width")?,
+                    required(name, &child, "height")?,
+                );
+                let data = child
+                    .children()
+                    .find(|data| data.has_tag_name("data"))
+                    .ok_or_else(|| invalid(name, "a layer has no data"))?;
+                let gids = match data.attribute("encoding") {
+                    Some(encoding) => decode_data(
+                        name,
+                        data.text().unwrap_or_default(),
+                        encoding,
+                        data.attribute("compression"),
+                    )?,
+                    None => data
+                        .children()
+                        .filter(|tile| tile.has_tag_name("tile"))
+                        .map(|tile| optional(&tile, "gid", 0))
+                        .collect(),
+                };
+
+                layers.push(checked_layer(
+                    name,
+                    TiledLayer {
+                        name: child
+                            .attribute("name")
+                            .unwrap_or_default()
+                            .to_owned(),
+                        width,
+                        height,
+                        opacity: child_opacity,
+                        visible: child_visible,
+                        data: gids,
+                    },
+                )?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads the tile layers of a `.tmj` map or group, flattening groups.
+///
+fn json_layers(
+    name: &str,
+    json: Vec<JsonLayer>,
+    opacity: f32,
+    visible: bool,
+    layers: &mut Vec<TiledLayer>,
+) -> Result<(), GraphicsError> {
+    for layer in json {
+        let layer_opacity = opacity * layer.opacity;
+        let layer_visible = visible && layer.visible;
+
+        match layer.kind.as_str() {
+            "group" => json_layers(
+                name,
+                layer.layers,
+                layer_opacity,
+                layer_visible,
+                layers,
+            )?,
+            "tilelayer" => {
+                let gids = match layer.data {
+                    Some(JsonData::Gids(gids)) => gids,
+                    Some(JsonData::Encoded(text)) => decode_data(
+                        name,
+                        &text,
+                        layer.encoding.as_deref().unwrap_or("base64"),
+                        layer.compression.as_deref(),
+                    )?,
+                    None => {
+                        return Err(invalid(name, "a layer has no data"));
+                    }
+                };
+
+                layers.push(checked_layer(
+                    name,
+                    TiledLayer {
+                        name: layer.name,
+                        width: layer.width,
+                        height: layer.height,
+                        opacity: layer_opacity,
+                        visible: layer_visible,
+                        data: gids,
+                    },
+                )?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Errors if the layer does not hold a GID for every tile.
+///
+fn checked_layer(
+    name: &str,
+    layer: TiledLayer,
+) -> Result<TiledLayer, GraphicsError> {
+    let Some(count) = (layer.width as usize).checked_mul(layer.height as usize)
+    else {
+        return Err(invalid(
+            name,
+            &format!("layer {} is too large", layer.name),
+        ));
+    };
+
+    if layer.data.len() != count {
+        return Err(invalid(
+            name,
+            &format!(
+                "layer {} has {} tiles instead of {count}",
+                layer.name,
+                layer.data.len(),
+            ),
+        ));
+    }
+
+    Ok(layer)
+}
+
+/// Decodes CSV or base64 layer data into GIDs.
+///
+fn decode_data(
+    name: &str,
+    text: &str,
+    encoding: &str,
+    compression: Option<&str>,
+) -> Result<Vec<u32>, GraphicsError> {
+    match encoding {
+        "csv" => text
+            .split(',')
+            .map(str::trim)
+            .filter(|gid| !gid.is_empty())
+            .map(|gid| {
+                gid.parse()
+                    .map_err(|_| invalid(name, &format!("invalid GID {gid}")))
+            })
+            .collect(),
+        "base64" => {
+            let raw = STANDARD.decode(text.trim())?;
+            let bytes = match compression.unwrap_or_default() {
+                "" => raw,
+                "zlib" => {
+                    let mut bytes = Vec::new();
+                    ZlibDecoder::new(raw.as_slice()).read_to_end(&mut bytes)?;
+                    bytes
+                }
+                "gzip" => {
+                    let mut bytes = Vec::new();
+                    GzDecoder::new(raw.as_slice()).read_to_end(&mut bytes)?;
+                    bytes
+                }
+                other => {
+                    return Err(invalid(
+                        name,
+                        &format!("{other} compression is not supported"),
+                    ));
+                }
+            };
+
+            Ok(bytes
+                .chunks_exact(4)
+                .map(|gid| u32::from_le_bytes([gid[0], gid[1], gid[2], gid[3]]))
+                .collect())
+        }
+        other => {
+            Err(invalid(name, &format!("{other} encoding is not supported")))
+        }
+    }
+}
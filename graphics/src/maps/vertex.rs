@@ -9,10 +9,20 @@ use rayon::{iter::repeatn, prelude::*};
 pub struct MapVertex {
     pub position: [f32; 3],
     pub tilesize: f32,
-    pub tile_id: u32,
+    /// X and Y of the tile within the texture excluding its padding.
+    pub tile_pos: [u32; 2],
     pub texture_layer: u32,
     pub color: u32,
     pub camera_type: u32,
+    /// Frame of the tile's animation this vertex shows.
+    pub frame: u32,
+    /// Frame count of the tile's animation. 0 or 1 if not animated.
+    pub frames: u32,
+    /// Milliseconds each frame is shown.
+    pub time: u32,
+    /// Frame of the four frame layer animation plus one. 0 if the layer is
+    /// always shown. See [`crate::MapLayerSettings::anim_frame`].
+    pub layer_frame: u32,
 }
 
 impl Default for MapVertex {
@@ -20,17 +30,21 @@ impl Default for MapVertex {
         Self {
             position: [0.0; 3],
             tilesize: 0.0,
-            tile_id: 0,
+            tile_pos: [0; 2],
             texture_layer: 0,
             color: 0,
             camera_type: 0,
+            frame: 0,
+            frames: 0,
+            time: 0,
+            layer_frame: 0,
         }
     }
 }
 
 impl BufferLayout for MapVertex {
     fn attributes() -> Vec<wgpu::VertexAttribute> {
-        wgpu::vertex_attr_array![1 => Float32x3, 2 => Float32, 3 => Uint32, 4 => Uint32, 5 => Uint32, 6 => Uint32]
+        wgpu::vertex_attr_array![1 => Float32x3, 2 => Float32, 3 => Uint32x2, 4 => Uint32, 5 => Uint32, 6 => Uint32, 7 => Uint32, 8 => Uint32, 9 => Uint32, 10 => Uint32]
             .to_vec()
     }
 
@@ -58,6 +72,6 @@ impl BufferLayout for MapVertex {
     }
 
     fn stride() -> usize {
-        std::mem::size_of::<[f32; 8]>()
+        std::mem::size_of::<[f32; 13]>()
     }
 }
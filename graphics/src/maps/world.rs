@@ -0,0 +1,432 @@
+use crate::{
+    AHashMap, AtlasSet, Bounds, CameraType, GpuRenderer, Map, MapLayout,
+    OrderedIndex, TileAnimation, TileData, Vec2,
+};
+
+/// X and Y of a chunk within a [`TileWorld`]. Chunk (0, 0) starts at the
+/// [`TileWorld`]'s origin.
+///
+pub type ChunkPos = (i32, i32);
+
+/// Fills and stores the chunks of a [`TileWorld`] while it streams.
+///
+/// Any `FnMut(ChunkPos, &mut Map)` closure can be used when nothing needs
+/// to happen on unload.
+///
+pub trait ChunkSource {
+    /// Sets the tiles of a chunk that is being loaded. The [`Map`] is
+    /// cleared and already placed at the chunk's position.
+    ///
+    fn load(&mut self, chunk: ChunkPos, map: &mut Map);
+
+    /// Called right before a chunk is unloaded, for example to save edits.
+    ///
+    fn unload(&mut self, _chunk: ChunkPos, _map: &Map) {}
+}
+
+impl<F> ChunkSource for F
+where
+    F: FnMut(ChunkPos, &mut Map),
+{
+    fn load(&mut self, chunk: ChunkPos, map: &mut Map) {
+        self(chunk, map)
+    }
+}
+
+/// Chunks that were loaded and unloaded by [`TileWorld::stream`].
+///
+#[derive(Clone, Debug, Default)]
+pub struct ChunkChanges {
+    pub loaded: Vec<ChunkPos>,
+    pub unloaded: Vec<ChunkPos>,
+}
+
+/// Large or endless tile world split into chunks, each of them a [`Map`]
+/// sharing the same [`MapLayout`].
+///
+/// Only the chunks around the camera are kept loaded. Unloaded chunks keep
+/// their buffer stores in a pool so loading a new chunk does not need to
+/// create new ones.
+///
+/// # Suggested usage
+/// Call [`TileWorld::stream`] with the visible [`Bounds`] whenever the
+/// camera moves, then call [`crate::MapRenderer::world_update`] each frame
+/// before [`crate::MapRenderer::finalize`].
+///
+pub struct TileWorld {
+    /// Loaded chunks.
+    chunks: AHashMap<ChunkPos, Map>,
+    /// Unloaded chunks kept to reuse their buffer stores.
+    pool: Vec<Map>,
+    /// Width, Height and Layers of every chunk.
+    layout: MapLayout,
+    /// The size of the Tile to render.
+    tilesize: u32,
+    /// Position chunk (0, 0) is rendered at.
+    origin: Vec2,
+    /// Extra chunks kept loaded around the visible ones.
+    margin: u32,
+    /// [`CameraType`] every chunk renders with.
+    camera_type: CameraType,
+    /// [`TileAnimation`]'s every chunk can play.
+    animations: Vec<TileAnimation>,
+}
+
+impl TileWorld {
+    /// Creates a new empty [`TileWorld`] whose chunks use tilesize and
+    /// [`MapLayout`].
+    ///
+    pub fn new(tilesize: u32, layout: MapLayout) -> Self {
+        Self {
+            chunks: AHashMap::default(),
+            pool: Vec::new(),
+            layout,
+            tilesize,
+            origin: Vec2::default(),
+            margin: 1,
+            camera_type: CameraType::None,
+            animations: Vec::new(),
+        }
+    }
+
+    /// Sets the extra chunks kept loaded around the visible ones when
+    /// building the [`TileWorld`]. Default is 1.
+    ///
+    pub fn with_margin(mut self, margin: u32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Sets the extra chunks kept loaded around the visible ones.
+    ///
+    pub fn set_margin(&mut self, margin: u32) -> &mut Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Sets the position chunk (0, 0) is rendered at and moves every loaded
+    /// chunk along.
+    ///
+    pub fn set_origin(&mut self, origin: Vec2) -> &mut Self {
+        let size = self.chunk_size();
+
+        self.origin = origin;
+
+        for (chunk, map) in self.chunks.iter_mut() {
+            map.set_position(
+                origin + Vec2::new(chunk.0 as f32, chunk.1 as f32) * size,
+            );
+        }
+
+        self
+    }
+
+    /// Sets the [`CameraType`] every chunk will use to Render with.
+    ///
+    pub fn set_camera_type(&mut self, camera_type: CameraType) -> &mut Self {
+        self.camera_type = camera_type;
+
+        for map in self.chunks.values_mut() {
+            map.set_camera_type(camera_type);
+        }
+
+        self
+    }
+
+    /// Sets the [`TileAnimation`]'s of every loaded and future chunk.
+    ///
+    pub fn set_animations(
+        &mut self,
+        animations: Vec<TileAnimation>,
+    ) -> &mut Self {
+        for map in self.chunks.values_mut() {
+            map.set_animations(animations.clone());
+        }
+
+        self.animations = animations;
+        self
+    }
+
+    /// Returns the [`TileAnimation`]'s every chunk can play.
+    ///
+    pub fn animations(&self) -> &[TileAnimation] {
+        &self.animations
+    }
+
+    /// Returns the [`MapLayout`] every chunk uses.
+    ///
+    pub fn layout(&self) -> &MapLayout {
+        &self.layout
+    }
+
+    /// Returns the size of the Tile to render.
+    ///
+    pub fn tilesize(&self) -> u32 {
+        self.tilesize
+    }
+
+    /// Returns the position chunk (0, 0) is rendered at.
+    ///
+    pub fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    /// Returns the Width and Height of a chunk in pixels.
+    ///
+    pub fn chunk_size(&self) -> Vec2 {
+        Vec2::new(
+            (self.layout.width * self.tilesize) as f32,
+            (self.layout.height * self.tilesize) as f32,
+        )
+    }
+
+    /// Returns the position a chunk is rendered at.
+    ///
+    pub fn chunk_position(&self, chunk: ChunkPos) -> Vec2 {
+        self.origin
+            + Vec2::new(chunk.0 as f32, chunk.1 as f32) * self.chunk_size()
+    }
+
+    /// Returns the chunk containing a position.
+    ///
+    pub fn chunk_at(&self, position: Vec2) -> ChunkPos {
+        let chunk = ((position - self.origin) / self.chunk_size()).floor();
+
+        (chunk.x as i32, chunk.y as i32)
+    }
+
+    /// Returns the chunk and the tile within it for a world tile position.
+    ///
+    pub fn tile_chunk(&self, x: i32, y: i32) -> (ChunkPos, (u32, u32)) {
+        let (width, height) =
+            (self.layout.width as i32, self.layout.height as i32);
+
+        (
+            (x.div_euclid(width), y.div_euclid(height)),
+            (x.rem_euclid(width) as u32, y.rem_euclid(height) as u32),
+        )
+    }
+
+    /// Returns the first and last chunk overlapping the [`Bounds`] including
+    /// the margin.
+    ///
+    pub fn visible_chunks(&self, bounds: &Bounds) -> (ChunkPos, ChunkPos) {
+        let margin = self.margin as i32;
+        let start = self.chunk_at(Vec2::new(bounds.left, bounds.bottom));
+        let end = self.chunk_at(Vec2::new(bounds.right, bounds.top));
+
+        (
+            (start.0 - margin, start.1 - margin),
+            (end.0 + margin, end.1 + margin),
+        )
+    }
+
+    /// Loads every chunk overlapping the [`Bounds`] plus the margin and
+    /// unloads all others. The [`Bounds`] are in the same coordinates as the
+    /// chunks' positions.
+    ///
+    /// Returns the chunks that were loaded and unloaded.
+    ///
+    pub fn stream(
+        &mut self,
+        renderer: &mut GpuRenderer,
+        bounds: &Bounds,
+        source: &mut impl ChunkSource,
+    ) -> ChunkChanges {
+        let (start, end) = self.visible_chunks(bounds);
+        let visible = |chunk: &ChunkPos| {
+            (start.0..=end.0).contains(&chunk.0)
+                && (start.1..=end.1).contains(&chunk.1)
+        };
+        let mut changes = ChunkChanges::default();
+
+        let unloaded: Vec<ChunkPos> = self
+            .chunks
+            .keys()
+            .copied()
+            .filter(|c| !visible(c))
+            .collect();
+
+        for chunk in unloaded {
+            if self.unload_chunk(chunk, source) {
+                changes.unloaded.push(chunk);
+            }
+        }
+
+        for y in start.1..=end.1 {
+            for x in start.0..=end.0 {
+                if !self.chunks.contains_key(&(x, y)) {
+                    self.load_chunk(renderer, (x, y), source);
+                    changes.loaded.push((x, y));
+                }
+            }
+        }
+
+        changes
+    }
+
+    /// Loads a chunk using a pooled [`Map`] when one exists. Returns the
+    /// already loaded chunk without calling the [`ChunkSource`] if it is
+    /// loaded.
+    ///
+    pub fn load_chunk(
+        &mut self,
+        renderer: &mut GpuRenderer,
+        chunk: ChunkPos,
+        source: &mut impl ChunkSource,
+    ) -> &mut Map {
+        let position = self.chunk_position(chunk);
+
+        self.chunks.entry(chunk).or_insert_with(|| {
+            let mut map = match self.pool.pop() {
+                Some(mut map) => {
+                    map.clear_tiles();
+                    map
+                }
+                None => Map::with_layout(
+                    renderer,
+                    self.tilesize,
+                    self.layout.clone(),
+                ),
+            };
+
+            map.set_position(position);
+            map.set_camera_type(self.camera_type);
+            map.set_animations(self.animations.clone());
+            source.load(chunk, &mut map);
+            map.can_render = true;
+            map
+        })
+    }
+
+    /// Unloads a chunk keeping its [`Map`] to be reused.
+    /// Returns false if the chunk was not loaded.
+    ///
+    pub fn unload_chunk(
+        &mut self,
+        chunk: ChunkPos,
+        source: &mut impl ChunkSource,
+    ) -> bool {
+        let Some(mut map) = self.chunks.remove(&chunk) else {
+            return false;
+        };
+
+        source.unload(chunk, &map);
+        map.can_render = false;
+        self.pool.push(map);
+        true
+    }
+
+    /// Returns a loaded chunk.
+    ///
+    pub fn chunk(&self, chunk: ChunkPos) -> Option<&Map> {
+        self.chunks.get(&chunk)
+    }
+
+    /// Returns a loaded chunk.
+    ///
+    pub fn chunk_mut(&mut self, chunk: ChunkPos) -> Option<&mut Map> {
+        self.chunks.get_mut(&chunk)
+    }
+
+    /// If the chunk is loaded.
+    ///
+    pub fn is_loaded(&self, chunk: ChunkPos) -> bool {
+        self.chunks.contains_key(&chunk)
+    }
+
+    /// Returns every loaded chunk.
+    ///
+    pub fn chunks(&self) -> impl Iterator<Item = (ChunkPos, &Map)> {
+        self.chunks.iter().map(|(chunk, map)| (*chunk, map))
+    }
+
+    /// Returns every loaded chunk.
+    ///
+    pub fn chunks_mut(&mut self) -> impl Iterator<Item = (ChunkPos, &mut Map)> {
+        self.chunks.iter_mut().map(|(chunk, map)| (*chunk, map))
+    }
+
+    /// Returns the amount of loaded chunks.
+    ///
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// If no chunks are loaded.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Returns the amount of unloaded [`Map`]'s kept for reuse.
+    ///
+    pub fn pooled(&self) -> usize {
+        self.pool.len()
+    }
+
+    /// gets the [`TileData`] based upon the tile's world x, y, and layer.
+    /// None if the chunk is not loaded or the layer does not exist.
+    ///
+    pub fn get_tile(&self, pos: (i32, i32, u32)) -> Option<TileData> {
+        let (chunk, (x, y)) = self.tile_chunk(pos.0, pos.1);
+        let index = self.layout.index(x, y, pos.2)?;
+
+        self.chunks.get(&chunk).map(|map| map.tiles[index])
+    }
+
+    /// Sets the tile at the world x, y, and layer within the chunk holding
+    /// it. Returns false if the chunk is not loaded or the layer does not
+    /// exist.
+    ///
+    pub fn set_tile(&mut self, pos: (i32, i32, u32), tile: TileData) -> bool {
+        let (chunk, (x, y)) = self.tile_chunk(pos.0, pos.1);
+
+        if pos.2 as usize >= self.layout.layer_count() {
+            return false;
+        }
+
+        match self.chunks.get_mut(&chunk) {
+            Some(map) => {
+                map.set_tile((x, y, pos.2), tile);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Used to check and update every loaded chunk's Buffer for Rendering.
+    /// Returns the Lower and Upper [`OrderedIndex`] of each chunk.
+    ///
+    pub fn update(
+        &mut self,
+        renderer: &mut GpuRenderer,
+        atlas: &mut AtlasSet,
+    ) -> Vec<[OrderedIndex; 2]> {
+        self.chunks
+            .values_mut()
+            .filter_map(|map| map.update(renderer, atlas))
+            .filter_map(|orders| orders.try_into().ok())
+            .collect()
+    }
+
+    /// Unloades every loaded and pooled chunk's buffers from the buffer
+    /// store without calling the [`ChunkSource`].
+    ///
+    pub fn unload(&mut self, renderer: &mut GpuRenderer) {
+        for map in self.chunks.values().chain(self.pool.iter()) {
+            map.unload(renderer);
+        }
+
+        self.chunks.clear();
+        self.pool.clear();
+    }
+
+    /// Unloades the pooled chunks' buffers from the buffer store.
+    ///
+    pub fn clear_pool(&mut self, renderer: &mut GpuRenderer) {
+        for map in self.pool.drain(..) {
+            map.unload(renderer);
+        }
+    }
+}
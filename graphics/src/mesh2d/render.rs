@@ -1,10 +1,13 @@
 use crate::{
-    AsBufferPass, GpuRenderer, GraphicsError, Mesh2D, Mesh2DRenderPipeline,
-    Mesh2DVertex, OrderedIndex, SetBuffers, System, VertexBuffer,
+    AsBufferPass, BlendMode, GpuRenderer, GraphicsError, Mesh2D,
+    Mesh2DRenderPipeline, Mesh2DVertex, OrderedIndex, SetBuffers, System,
+    VertexBuffer,
 };
 
 pub struct Mesh2DRenderer {
     pub vbos: VertexBuffer<Mesh2DVertex>,
+    /// [`BlendMode`] the Renderer draws with.
+    blend_mode: BlendMode,
 }
 
 impl Mesh2DRenderer {
@@ -13,9 +16,34 @@ impl Mesh2DRenderer {
     pub fn new(renderer: &GpuRenderer) -> Result<Self, GraphicsError> {
         Ok(Self {
             vbos: VertexBuffer::new(renderer.gpu_device(), 512),
+            blend_mode: BlendMode::Alpha,
         })
     }
 
+    /// Creates a new [`Mesh2DRenderer`] that draws using a [`BlendMode`].
+    /// Creates the [`Mesh2DRenderPipeline`] for the mode if it does not yet exist.
+    ///
+    /// # Arguments
+    /// - blend_mode: [`BlendMode`] used to draw with.
+    ///
+    pub fn with_blend_mode(
+        renderer: &mut GpuRenderer,
+        blend_mode: BlendMode,
+    ) -> Result<Self, GraphicsError> {
+        renderer.create_pipeline(Mesh2DRenderPipeline::new(blend_mode));
+
+        let mut this = Self::new(renderer)?;
+
+        this.blend_mode = blend_mode;
+        Ok(this)
+    }
+
+    /// Returns the [`BlendMode`] the Renderer draws with.
+    ///
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
     /// Adds a Buffer [`OrderedIndex`] to the Rendering Store to get processed.
     /// This must be done before [`Mesh2DRenderer::finalize`] but after [`Mesh2D::update`] in order for it to Render.
     ///
@@ -99,7 +127,11 @@ where
             if !vbos.is_empty() {
                 self.set_buffers(buffer.vbos.as_buffer_pass());
                 self.set_pipeline(
-                    renderer.get_pipelines(Mesh2DRenderPipeline).unwrap(),
+                    renderer
+                        .get_pipelines(Mesh2DRenderPipeline::new(
+                            buffer.blend_mode,
+                        ))
+                        .unwrap(),
                 );
 
                 if buffer.vbos.is_clipped() {
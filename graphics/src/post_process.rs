@@ -0,0 +1,546 @@
+mod effect;
+mod pipeline;
+
+pub use effect::*;
+pub use pipeline::*;
+
+use crate::{GpuRenderer, Pass, RenderTarget};
+use std::sync::Arc;
+
+/// What a pass reads from.
+///
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Source {
+    /// The [`PostProcess::scene`] Target.
+    Scene,
+    /// Output of an earlier pass.
+    Pass(usize),
+    /// The [`ColorLut`] of the effect at the index.
+    Lut(usize),
+}
+
+/// A Pass before its targets got assigned.
+///
+#[derive(PartialEq)]
+struct PassDesc {
+    shader: Option<PostShader>,
+    effect: Option<usize>,
+    phase: u32,
+    input: Source,
+    second: Source,
+}
+
+/// Color only single sampled Texture the passes render into and read
+/// from. Unlike a [`RenderTarget`] it has no Depth or Multisampled Buffer
+/// as the full screen passes use neither.
+///
+struct PostTarget {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+}
+
+impl PostTarget {
+    fn new(renderer: &GpuRenderer, width: u32, height: u32) -> Self {
+        // Opengl can not tell a single layer Array apart from a 2D Texture.
+        let layers = if renderer.backend == wgpu::Backend::Gl {
+            2
+        } else {
+            1
+        };
+        let format = renderer.surface_format();
+
+        let texture =
+            renderer.device().create_texture(&wgpu::TextureDescriptor {
+                label: Some("Post Process Target"),
+                size: wgpu::Extent3d {
+                    width: width.max(1),
+                    height: height.max(1),
+                    depth_or_array_layers: layers,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[format],
+            });
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor {
+            label: Some("Post Process Target View"),
+            dimension: Some(wgpu::TextureViewDimension::D2),
+            base_array_layer: 0,
+            array_layer_count: Some(1),
+            ..Default::default()
+        });
+
+        Self { texture, view }
+    }
+}
+
+/// A single full screen pass of the [`PostProcess`] stack.
+///
+struct PostStep {
+    /// Built in Pipeline or None when the effect is a [`CustomPostEffect`].
+    pipeline: Option<PostProcessPipeline>,
+    effect: Option<usize>,
+    phase: u32,
+    /// Index into the ping pong targets or None for the frame buffer.
+    output: Option<usize>,
+    buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+/// Stack of full screen effects applied to a scene before it gets
+/// rendered onto the frame buffer.
+///
+/// Render the scene into [`PostProcess::scene`], call
+/// [`PostProcess::finalize`] and then run it as a [`Pass`]. Each effect
+/// reads the output of the one before it using ping pong Textures and the
+/// last pass writes into the frame buffer.
+///
+pub struct PostProcess {
+    effects: Vec<PostEffect>,
+    /// Target the scene gets rendered into.
+    scene: RenderTarget,
+    /// Intermediate targets passes read from and write into.
+    targets: Vec<PostTarget>,
+    steps: Vec<PostStep>,
+    sampler: wgpu::Sampler,
+    layout: Arc<wgpu::BindGroupLayout>,
+    /// Passes the steps were built from.
+    passes: Vec<PassDesc>,
+    /// Lookup Tables the steps were built with.
+    luts: Vec<wgpu::TextureView>,
+    /// If the effects got changed and the passes need rebuilding.
+    changed: bool,
+}
+
+impl PostProcess {
+    /// Creates an empty [`PostProcess`] stack sized to the renderer.
+    ///
+    pub fn new(renderer: &mut GpuRenderer) -> Self {
+        for shader in [
+            PostShader::Copy,
+            PostShader::Blur,
+            PostShader::BloomThreshold,
+            PostShader::BloomComposite,
+            PostShader::Vignette,
+            PostShader::ColorGrade,
+            PostShader::Fade,
+        ] {
+            renderer.create_pipeline(PostProcessPipeline::new(shader));
+        }
+
+        let (width, height) = Self::target_size(renderer);
+        let layout = renderer.create_layout(PostProcessLayout);
+        let sampler =
+            renderer.device().create_sampler(&wgpu::SamplerDescriptor {
+                label: Some("Post Process sampler"),
+                address_mode_u: wgpu::AddressMode::ClampToEdge,
+                address_mode_v: wgpu::AddressMode::ClampToEdge,
+                address_mode_w: wgpu::AddressMode::ClampToEdge,
+                mag_filter: wgpu::FilterMode::Linear,
+                min_filter: wgpu::FilterMode::Linear,
+                mipmap_filter: wgpu::FilterMode::Nearest,
+                lod_max_clamp: 0.0,
+                ..Default::default()
+            });
+
+        Self {
+            effects: Vec::new(),
+            scene: RenderTarget::new_linear(renderer, width, height),
+            targets: Vec::new(),
+            steps: Vec::new(),
+            sampler,
+            layout,
+            passes: Vec::new(),
+            luts: Vec::new(),
+            changed: true,
+        }
+    }
+
+    fn target_size(renderer: &GpuRenderer) -> (u32, u32) {
+        let size = renderer.size();
+
+        (size.width as u32, size.height as u32)
+    }
+
+    /// Returns the [`RenderTarget`] the scene must be rendered into.
+    ///
+    pub fn scene(&self) -> &RenderTarget {
+        &self.scene
+    }
+
+    /// Returns the effects in the order they get applied.
+    ///
+    pub fn effects(&self) -> &[PostEffect] {
+        &self.effects
+    }
+
+    /// Returns a mutable reference to the effect at index to change its
+    /// parameters. Replacing it with another kind of effect or another
+    /// [`ColorLut`] rebuilds the passes on the next
+    /// [`PostProcess::finalize`].
+    ///
+    pub fn effect_mut(&mut self, index: usize) -> Option<&mut PostEffect> {
+        self.effects.get_mut(index)
+    }
+
+    /// Adds an effect to the end of the stack. Returns its index.
+    ///
+    pub fn push(&mut self, effect: PostEffect) -> usize {
+        self.effects.push(effect);
+        self.changed = true;
+        self.effects.len() - 1
+    }
+
+    /// Inserts an effect at index.
+    ///
+    pub fn insert(&mut self, index: usize, effect: PostEffect) {
+        self.effects.insert(index.min(self.effects.len()), effect);
+        self.changed = true;
+    }
+
+    /// Removes the effect at index.
+    ///
+    pub fn remove(&mut self, index: usize) -> Option<PostEffect> {
+        if index >= self.effects.len() {
+            return None;
+        }
+
+        self.changed = true;
+        Some(self.effects.remove(index))
+    }
+
+    /// Removes all effects. The scene then gets copied to the frame as is.
+    ///
+    pub fn clear(&mut self) {
+        self.effects.clear();
+        self.changed = true;
+    }
+
+    /// Resizes the targets to the renderer, rebuilds the passes if the
+    /// effects changed and uploads each passes parameters.
+    /// Must be called each frame before rendering.
+    ///
+    pub fn finalize(&mut self, renderer: &mut GpuRenderer) {
+        let (width, height) = Self::target_size(renderer);
+
+        if self.scene.size() != (width.max(1), height.max(1))
+            || self.scene.sample_count() != renderer.sample_count()
+        {
+            self.scene.resize(renderer, width, height);
+
+            for target in &mut self.targets {
+                *target = PostTarget::new(renderer, width, height);
+            }
+
+            self.changed = true;
+        }
+
+        let passes = self.describe();
+        let luts = self.lut_views();
+
+        if self.changed || passes != self.passes || luts != self.luts {
+            self.rebuild(renderer, &passes);
+            self.passes = passes;
+            self.luts = luts;
+            self.changed = false;
+        }
+
+        for step in &self.steps {
+            let params = match step.effect.and_then(|i| self.effects.get(i)) {
+                Some(effect) => effect.params(step.phase),
+                None => [0.0; 16],
+            };
+
+            renderer.queue().write_buffer(
+                &step.buffer,
+                0,
+                bytemuck::cast_slice(&params),
+            );
+        }
+    }
+
+    /// Returns the views of every [`ColorLut`] in use.
+    ///
+    fn lut_views(&self) -> Vec<wgpu::TextureView> {
+        self.effects
+            .iter()
+            .filter_map(|effect| match effect {
+                PostEffect::ColorGrade { lut, .. } => Some(lut.view.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Breaks the effects down into passes.
+    ///
+    fn describe(&self) -> Vec<PassDesc> {
+        let mut descs: Vec<PassDesc> = Vec::new();
+        let mut current = Source::Scene;
+        let push = |descs: &mut Vec<PassDesc>,
+                    shader,
+                    effect,
+                    phase,
+                    input,
+                    second| {
+            descs.push(PassDesc {
+                shader,
+                effect,
+                phase,
+                input,
+                second,
+            });
+            Source::Pass(descs.len() - 1)
+        };
+
+        for (index, effect) in self.effects.iter().enumerate() {
+            let i = Some(index);
+
+            current = match effect {
+                PostEffect::GaussianBlur { .. } => {
+                    let blur = Some(PostShader::Blur);
+                    let h = push(&mut descs, blur, i, 0, current, current);
+                    push(&mut descs, blur, i, 1, h, h)
+                }
+                PostEffect::Bloom { .. } => {
+                    let blur = Some(PostShader::Blur);
+                    let bright = push(
+                        &mut descs,
+                        Some(PostShader::BloomThreshold),
+                        i,
+                        0,
+                        current,
+                        current,
+                    );
+                    let h = push(&mut descs, blur, i, 1, bright, bright);
+                    let v = push(&mut descs, blur, i, 2, h, h);
+                    push(
+                        &mut descs,
+                        Some(PostShader::BloomComposite),
+                        i,
+                        3,
+                        v,
+                        current,
+                    )
+                }
+                PostEffect::Vignette { .. } => push(
+                    &mut descs,
+                    Some(PostShader::Vignette),
+                    i,
+                    0,
+                    current,
+                    current,
+                ),
+                PostEffect::ColorGrade { .. } => push(
+                    &mut descs,
+                    Some(PostShader::ColorGrade),
+                    i,
+                    0,
+                    current,
+                    Source::Lut(index),
+                ),
+                PostEffect::ScreenFade { .. } => push(
+                    &mut descs,
+                    Some(PostShader::Fade),
+                    i,
+                    0,
+                    current,
+                    current,
+                ),
+                PostEffect::Custom(_) => {
+                    push(&mut descs, None, i, 0, current, current)
+                }
+            };
+        }
+
+        if descs.is_empty() {
+            push(
+                &mut descs,
+                Some(PostShader::Copy),
+                None,
+                0,
+                Source::Scene,
+                Source::Scene,
+            );
+        }
+
+        descs
+    }
+
+    /// Rebuilds the passes, assigning each one a target that no later
+    /// pass still needs to read from.
+    ///
+    fn rebuild(&mut self, renderer: &mut GpuRenderer, descs: &[PassDesc]) {
+        let count = descs.len();
+        let mut last_use = vec![0; count];
+
+        for (index, desc) in descs.iter().enumerate() {
+            for source in [desc.input, desc.second] {
+                if let Source::Pass(pass) = source {
+                    last_use[pass] = last_use[pass].max(index);
+                }
+            }
+        }
+
+        // Which pass output each target currently holds.
+        let mut holders: Vec<usize> = Vec::new();
+        let mut outputs = vec![None; count];
+
+        for (index, output) in outputs.iter_mut().enumerate().take(count - 1) {
+            let slot = match holders.iter().position(|&h| last_use[h] < index) {
+                Some(slot) => {
+                    holders[slot] = index;
+                    slot
+                }
+                None => {
+                    holders.push(index);
+                    holders.len() - 1
+                }
+            };
+
+            *output = Some(slot);
+        }
+
+        let (width, height) = Self::target_size(renderer);
+
+        self.targets.truncate(holders.len());
+
+        while self.targets.len() < holders.len() {
+            self.targets.push(PostTarget::new(renderer, width, height));
+        }
+
+        let views: Vec<wgpu::TextureView> =
+            std::iter::once(&self.scene.texture)
+                .chain(self.targets.iter().map(|target| &target.texture))
+                .map(|texture| {
+                    texture.create_view(&wgpu::TextureViewDescriptor {
+                        label: Some("Post Process Input"),
+                        dimension: Some(wgpu::TextureViewDimension::D2Array),
+                        ..Default::default()
+                    })
+                })
+                .collect();
+
+        self.steps = descs
+            .iter()
+            .enumerate()
+            .map(|(index, desc)| {
+                let view = |source: Source| match source {
+                    Source::Scene => &views[0],
+                    Source::Pass(pass) => {
+                        &views[outputs[pass].unwrap_or_default() + 1]
+                    }
+                    Source::Lut(effect) => match &self.effects[effect] {
+                        PostEffect::ColorGrade { lut, .. } => &lut.view,
+                        _ => &views[0],
+                    },
+                };
+
+                let buffer =
+                    renderer.device().create_buffer(&wgpu::BufferDescriptor {
+                        label: Some("Post Process params"),
+                        size: 64,
+                        usage: wgpu::BufferUsages::UNIFORM
+                            | wgpu::BufferUsages::COPY_DST,
+                        mapped_at_creation: false,
+                    });
+
+                let bind_group = renderer.device().create_bind_group(
+                    &wgpu::BindGroupDescriptor {
+                        label: Some("Post Process bind group"),
+                        layout: &self.layout,
+                        entries: &[
+                            wgpu::BindGroupEntry {
+                                binding: 0,
+                                resource: wgpu::BindingResource::TextureView(
+                                    view(desc.input),
+                                ),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 1,
+                                resource: wgpu::BindingResource::Sampler(
+                                    &self.sampler,
+                                ),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 2,
+                                resource: buffer.as_entire_binding(),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 3,
+                                resource: wgpu::BindingResource::TextureView(
+                                    view(desc.second),
+                                ),
+                            },
+                        ],
+                    },
+                );
+
+                PostStep {
+                    pipeline: desc.shader.map(PostProcessPipeline::new),
+                    effect: desc.effect,
+                    phase: desc.phase,
+                    output: outputs[index],
+                    buffer,
+                    bind_group,
+                }
+            })
+            .collect();
+    }
+}
+
+impl Pass for PostProcess {
+    fn render(
+        &mut self,
+        renderer: &GpuRenderer,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let Some(frame) = renderer.frame_buffer() else {
+            return;
+        };
+
+        for step in &self.steps {
+            let pipeline = match step.pipeline {
+                Some(pipeline) => renderer.get_pipelines(pipeline),
+                None => match step.effect.and_then(|i| self.effects.get(i)) {
+                    Some(PostEffect::Custom(custom)) => Some(&custom.pipeline),
+                    _ => None,
+                },
+            };
+
+            let Some(pipeline) = pipeline else {
+                continue;
+            };
+
+            let view = match step.output {
+                Some(index) => &self.targets[index].view,
+                None => frame,
+            };
+
+            let mut pass =
+                encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Post Process pass"),
+                    color_attachments: &[Some(
+                        wgpu::RenderPassColorAttachment {
+                            view,
+                            resolve_target: None,
+                            ops: wgpu::Operations {
+                                load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                                store: wgpu::StoreOp::Store,
+                            },
+                        },
+                    )],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &step.bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use crate::{
+    GpuRenderer, GraphicsError, OtherError, POST_PROCESS_HEADER,
+    PostProcessLayout, Texture, post_process::pipeline::create_post_pipeline,
+};
+use cosmic_text::Color;
+
+/// A single effect within the [`crate::PostProcess`] stack.
+/// Parameters can be changed every frame without rebuilding the stack.
+///
+pub enum PostEffect {
+    /// Separable gaussian blur.
+    GaussianBlur {
+        /// Blur radius in pixels. Clamped to 32.
+        radius: f32,
+    },
+    /// Adds a blurred copy of the bright areas back onto the image.
+    Bloom {
+        /// Brightness at which pixels start to bloom.
+        threshold: f32,
+        /// Range above the threshold used to fade the bloom in.
+        knee: f32,
+        /// Blur radius in pixels of the bloom. Clamped to 32.
+        radius: f32,
+        /// Strength the bloom gets added with.
+        intensity: f32,
+    },
+    /// Darkens the edges of the screen towards a color.
+    Vignette {
+        /// Color the edges fade into. Alpha scales the effect.
+        color: Color,
+        /// Distance from the center, 0.0 to 1.0, the vignette starts at.
+        radius: f32,
+        /// Distance over which the vignette fades in.
+        softness: f32,
+        /// Strength of the vignette.
+        intensity: f32,
+    },
+    /// Remaps colors using a [`ColorLut`].
+    ColorGrade {
+        /// Lookup Table to grade with.
+        lut: ColorLut,
+        /// Blend between the original, 0.0, and graded, 1.0, colors.
+        intensity: f32,
+    },
+    /// Fades the whole screen towards a color.
+    ScreenFade {
+        /// Color to fade towards. Alpha scales the effect.
+        color: Color,
+        /// Fade amount from 0.0 to 1.0.
+        amount: f32,
+    },
+    /// A user supplied WGSL pass.
+    Custom(CustomPostEffect),
+}
+
+impl PostEffect {
+    /// Returns the uniform parameters of a pass of the effect.
+    ///
+    pub(crate) fn params(&self, phase: u32) -> [f32; 16] {
+        let mut params = [0.0; 16];
+
+        match self {
+            PostEffect::GaussianBlur { radius } => {
+                Self::blur_params(&mut params, phase, *radius);
+            }
+            PostEffect::Bloom {
+                threshold,
+                knee,
+                radius,
+                intensity,
+            } => match phase {
+                0 => params[..2].copy_from_slice(&[*threshold, *knee]),
+                1 | 2 => Self::blur_params(&mut params, phase - 1, *radius),
+                _ => params[0] = *intensity,
+            },
+            PostEffect::Vignette {
+                color,
+                radius,
+                softness,
+                intensity,
+            } => {
+                params[..4].copy_from_slice(&color_to_linear(*color));
+                params[4..7].copy_from_slice(&[*radius, *softness, *intensity]);
+            }
+            PostEffect::ColorGrade { lut, intensity } => {
+                params[..2].copy_from_slice(&[lut.size as f32, *intensity]);
+            }
+            PostEffect::ScreenFade { color, amount } => {
+                params[..4].copy_from_slice(&color_to_linear(*color));
+                params[4] = *amount;
+            }
+            PostEffect::Custom(custom) => params = custom.params,
+        }
+
+        params
+    }
+
+    fn blur_params(params: &mut [f32; 16], phase: u32, radius: f32) {
+        let direction = if phase == 0 { [1.0, 0.0] } else { [0.0, 1.0] };
+
+        params[..3].copy_from_slice(&[direction[0], direction[1], radius]);
+    }
+}
+
+/// Converts a sRGB [`Color`] into linear float channels.
+///
+fn color_to_linear(color: Color) -> [f32; 4] {
+    let channel = |c: u8| {
+        let c = c as f32 / 255.0;
+
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+
+    [
+        channel(color.r()),
+        channel(color.g()),
+        channel(color.b()),
+        color.a() as f32 / 255.0,
+    ]
+}
+
+/// Color grading Lookup Table stored as a horizontal strip of size slices,
+/// each size by size pixels, with blue increasing per slice.
+///
+pub struct ColorLut {
+    /// Texture in GRAM holding the Table.
+    pub texture: wgpu::Texture,
+    /// View used to sample the Table.
+    pub view: wgpu::TextureView,
+    /// Amount of entries per color channel.
+    size: u32,
+}
+
+impl ColorLut {
+    /// Creates a [`ColorLut`] from a [`Texture`] of size * size by size.
+    ///
+    pub fn new(
+        renderer: &GpuRenderer,
+        texture: &Texture,
+    ) -> Result<Self, GraphicsError> {
+        let (width, height) = texture.size();
+
+        if height < 2 || width != height * height {
+            return Err(GraphicsError::Other(OtherError::new(
+                "Color LUT width must be its height squared.",
+            )));
+        }
+
+        Ok(Self::from_bytes(renderer, height, texture.bytes()))
+    }
+
+    /// Creates a [`ColorLut`] that does not change any colors.
+    ///
+    pub fn identity(renderer: &GpuRenderer, size: u32) -> Self {
+        let size = size.max(2);
+        let max = (size - 1) as f32;
+        let mut bytes = Vec::with_capacity((size * size * size * 4) as usize);
+
+        for g in 0..size {
+            for b in 0..size {
+                for r in 0..size {
+                    bytes.extend_from_slice(&[
+                        (r as f32 / max * 255.0).round() as u8,
+                        (g as f32 / max * 255.0).round() as u8,
+                        (b as f32 / max * 255.0).round() as u8,
+                        255,
+                    ]);
+                }
+            }
+        }
+
+        Self::from_bytes(renderer, size, &bytes)
+    }
+
+    fn from_bytes(renderer: &GpuRenderer, size: u32, bytes: &[u8]) -> Self {
+        let extent = wgpu::Extent3d {
+            width: size * size,
+            height: size,
+            depth_or_array_layers: 1,
+        };
+
+        // Opengl can not tell a single layer Array apart from a 2D Texture.
+        let layers = if renderer.backend == wgpu::Backend::Gl {
+            2
+        } else {
+            1
+        };
+
+        let texture =
+            renderer.device().create_texture(&wgpu::TextureDescriptor {
+                label: Some("Color LUT"),
+                size: wgpu::Extent3d {
+                    depth_or_array_layers: layers,
+                    ..extent
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            });
+
+        renderer.queue().write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            bytes,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * size * size),
+                rows_per_image: Some(size),
+            },
+            extent,
+        );
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor {
+            label: Some("Color LUT View"),
+            dimension: Some(wgpu::TextureViewDimension::D2Array),
+            ..Default::default()
+        });
+
+        Self {
+            texture,
+            view,
+            size,
+        }
+    }
+
+    /// Returns the amount of entries per color channel.
+    ///
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+}
+
+/// User supplied post process pass.
+///
+/// The WGSL source only needs a `fn fragment(in: VertexOutput) -> vec4<f32>`
+/// returning a linear color, everything in [`crate::POST_PROCESS_HEADER`]
+/// is prepended to it. The params get passed as `params.data0` to `data3`.
+///
+pub struct CustomPostEffect {
+    /// Parameters passed into the shader each frame.
+    pub params: [f32; 16],
+    pub(crate) pipeline: wgpu::RenderPipeline,
+}
+
+impl CustomPostEffect {
+    /// Compiles a [`CustomPostEffect`] from a WGSL fragment source.
+    ///
+    /// Returns [`GraphicsError::InvalidShader`] with the compiler's message
+    /// if the source does not parse or validate.
+    ///
+    pub fn new(
+        renderer: &mut GpuRenderer,
+        fragment_source: &str,
+    ) -> Result<Self, GraphicsError> {
+        let source = format!("{POST_PROCESS_HEADER}{fragment_source}");
+        let module = naga::front::wgsl::parse_str(&source).map_err(|e| {
+            GraphicsError::InvalidShader(e.emit_to_string(&source))
+        })?;
+
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::default(),
+        )
+        .validate(&module)
+        .map_err(|e| GraphicsError::InvalidShader(e.emit_to_string(&source)))?;
+
+        let layout = renderer.create_layout(PostProcessLayout);
+
+        Ok(Self {
+            params: [0.0; 16],
+            pipeline: create_post_pipeline(
+                renderer.device(),
+                &layout,
+                renderer.surface_format(),
+                fragment_source,
+            ),
+        })
+    }
+
+    /// Sets the parameters passed into the shader.
+    ///
+    pub fn set_params(&mut self, params: [f32; 16]) -> &mut Self {
+        self.params = params;
+        self
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{
+    GpuDevice, Layout, LayoutStorage, PipeLineLayout, gamma_constants,
+};
+use bytemuck::{Pod, Zeroable};
+
+/// Shared header of every post process shader. Contains the full screen
+/// vertex and fragment entry points, the bindings and the `sample_input`,
+/// `sample_second`, `linear_to_srgb` and `srgb_to_linear` helpers.
+///
+pub const POST_PROCESS_HEADER: &str =
+    include_str!("../shaders/post/common.wgsl");
+
+/// Built in shaders of the [`crate::PostProcess`] stack.
+///
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PostShader {
+    Copy,
+    Blur,
+    BloomThreshold,
+    BloomComposite,
+    Vignette,
+    ColorGrade,
+    Fade,
+}
+
+impl PostShader {
+    /// Returns the fragment source of the shader.
+    ///
+    fn source(self) -> &'static str {
+        match self {
+            PostShader::Copy => include_str!("../shaders/post/copy.wgsl"),
+            PostShader::Blur => include_str!("../shaders/post/blur.wgsl"),
+            PostShader::BloomThreshold => {
+                include_str!("../shaders/post/bloom_threshold.wgsl")
+            }
+            PostShader::BloomComposite => {
+                include_str!("../shaders/post/bloom_composite.wgsl")
+            }
+            PostShader::Vignette => {
+                include_str!("../shaders/post/vignette.wgsl")
+            }
+            PostShader::ColorGrade => {
+                include_str!("../shaders/post/color_grade.wgsl")
+            }
+            PostShader::Fade => include_str!("../shaders/post/fade.wgsl"),
+        }
+    }
+
+    fn from_u32(value: u32) -> Self {
+        match value {
+            1 => PostShader::Blur,
+            2 => PostShader::BloomThreshold,
+            3 => PostShader::BloomComposite,
+            4 => PostShader::Vignette,
+            5 => PostShader::ColorGrade,
+            6 => PostShader::Fade,
+            _ => PostShader::Copy,
+        }
+    }
+}
+
+/// Texture, Sampler and Parameter Layout shared by all post process passes.
+///
+#[repr(C)]
+#[derive(Clone, Copy, Hash, Pod, Zeroable)]
+pub struct PostProcessLayout;
+
+impl Layout for PostProcessLayout {
+    fn create_layout(
+        &self,
+        gpu_device: &mut GpuDevice,
+    ) -> wgpu::BindGroupLayout {
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                multisampled: false,
+                view_dimension: wgpu::TextureViewDimension::D2Array,
+                sample_type: wgpu::TextureSampleType::Float {
+                    filterable: true,
+                },
+            },
+            count: None,
+        };
+
+        gpu_device.device().create_bind_group_layout(
+            &wgpu::BindGroupLayoutDescriptor {
+                label: Some("post_process_bind_group_layout"),
+                entries: &[
+                    texture_entry(0),
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(
+                            wgpu::SamplerBindingType::Filtering,
+                        ),
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    texture_entry(3),
+                ],
+            },
+        )
+    }
+}
+
+/// [`crate::PostProcess`] RenderPipeline Layout of a built in
+/// [`PostShader`].
+///
+#[repr(C)]
+#[derive(Clone, Copy, Hash, Pod, Zeroable)]
+pub struct PostProcessPipeline {
+    shader: u32,
+}
+
+impl PostProcessPipeline {
+    /// Creates the Pipeline Layout key for a [`PostShader`].
+    ///
+    pub fn new(shader: PostShader) -> Self {
+        Self {
+            shader: shader as u32,
+        }
+    }
+}
+
+impl PipeLineLayout for PostProcessPipeline {
+    fn create_layout(
+        &self,
+        gpu_device: &mut GpuDevice,
+        layouts: &mut LayoutStorage,
+        surface_format: wgpu::TextureFormat,
+    ) -> wgpu::RenderPipeline {
+        let layout = layouts.create_layout(gpu_device, PostProcessLayout);
+        let source = PostShader::from_u32(self.shader).source();
+
+        create_post_pipeline(
+            gpu_device.device(),
+            &layout,
+            surface_format,
+            source,
+        )
+    }
+}
+
+/// Creates a full screen [`wgpu::RenderPipeline`] from a fragment shader
+/// source that gets appended to the [`POST_PROCESS_HEADER`].
+///
+pub(crate) fn create_post_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    surface_format: wgpu::TextureFormat,
+    fragment_source: &str,
+) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("Post Process Shader"),
+        source: wgpu::ShaderSource::Wgsl(
+            format!("{POST_PROCESS_HEADER}{fragment_source}").into(),
+        ),
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Post process render pipeline"),
+        layout: Some(&device.create_pipeline_layout(
+            &wgpu::PipelineLayoutDescriptor {
+                label: Some("post_process_pipeline_layout"),
+                bind_group_layouts: &[layout],
+                push_constant_ranges: &[],
+            },
+        )),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vertex"),
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            unclipped_depth: false,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("post_fragment"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: surface_format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants: gamma_constants(surface_format),
+                ..Default::default()
+            },
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
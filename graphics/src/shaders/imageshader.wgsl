@@ -63,6 +63,31 @@ fn srgb_to_linear(c: f32) -> f32 {
     }
 }
 
+// Set by the Renderer when the surface is not sRGB so colors get gamma
+// encoded by hand.
+override manual_gamma: bool = false;
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        return c * 12.92;
+    } else {
+        return 1.055 * pow(c, 1.0 / 2.4) - 0.055;
+    }
+}
+
+fn output_color(color: vec4<f32>) -> vec4<f32> {
+    if manual_gamma {
+        return vec4<f32>(
+            linear_to_srgb(color.r),
+            linear_to_srgb(color.g),
+            linear_to_srgb(color.b),
+            color.a,
+        );
+    }
+
+    return color;
+}
+
 fn unpack_color(color: u32) -> vec4<f32> {
     return vec4<f32>(
         srgb_to_linear(f32((color & 0xff0000u) >> 16u) / 255.0),
@@ -247,6 +272,15 @@ fn vertex(
     return result;
 }
 
+// Picks the mip level from how many texels one screen pixel covers.
+// Clamped to 0 by the sampler when the texture is not mipmapped.
+fn mip_level(texel_coords: vec2<f32>) -> f32 {
+    let dx = dpdx(texel_coords);
+    let dy = dpdy(texel_coords);
+
+    return max(0.0, 0.5 * log2(max(dot(dx, dx), dot(dy, dy))));
+}
+
 // Fragment shader
 @fragment
 fn fragment(vertex: VertexOutput,) -> @location(0) vec4<f32> {
@@ -259,11 +293,12 @@ fn fragment(vertex: VertexOutput,) -> @location(0) vec4<f32> {
         vertex.animate > 0u
     );
 
-    let object_color = textureSampleLevel(tex, tex_sample ,coords / vertex.size, vertex.layer, 1.0) * vertex.col;
+    let level = mip_level(coords);
+    let object_color = textureSampleLevel(tex, tex_sample ,coords / vertex.size, vertex.layer, level) * vertex.col;
 
     if (object_color.a <= 0.0) {
         discard;
     }
 
-    return object_color;
+    return output_color(object_color);
 }
\ No newline at end of file
@@ -80,6 +80,31 @@ fn srgb_to_linear(c: f32) -> f32 {
     }
 }
 
+// Set by the Renderer when the surface is not sRGB so colors get gamma
+// encoded by hand.
+override manual_gamma: bool = false;
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        return c * 12.92;
+    } else {
+        return 1.055 * pow(c, 1.0 / 2.4) - 0.055;
+    }
+}
+
+fn output_color(color: vec4<f32>) -> vec4<f32> {
+    if manual_gamma {
+        return vec4<f32>(
+            linear_to_srgb(color.r),
+            linear_to_srgb(color.g),
+            linear_to_srgb(color.b),
+            color.a,
+        );
+    }
+
+    return color;
+}
+
 fn unpack_color(color: u32) -> vec4<f32> {
     return vec4<f32>(
         srgb_to_linear(f32((color & 0xff0000u) >> 16u) / 255.0),
@@ -290,5 +315,5 @@ fn fragment(vertex: VertexOutput,) -> @location(0) vec4<f32> {
         discard;
     }
 
-    return col;
+    return output_color(col);
 }
\ No newline at end of file
@@ -19,10 +19,14 @@ struct VertexInput {
     @location(0) v_pos: vec2<f32>,
     @location(1) position: vec3<f32>,
     @location(2) tilesize: f32,
-    @location(3) tile_id: u32,
+    @location(3) tile_pos: vec2<u32>,
     @location(4) texture_layer: u32,
     @location(5) color: u32,
     @location(6) camera_type: u32,
+    @location(7) frame: u32,
+    @location(8) frames: u32,
+    @location(9) time: u32,
+    @location(10) layer_frame: u32,
 };
 
 struct VertexOutput {
@@ -47,6 +51,31 @@ fn srgb_to_linear(c: f32) -> f32 {
     }
 }
 
+// Set by the Renderer when the surface is not sRGB so colors get gamma
+// encoded by hand.
+override manual_gamma: bool = false;
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        return c * 12.92;
+    } else {
+        return 1.055 * pow(c, 1.0 / 2.4) - 0.055;
+    }
+}
+
+fn output_color(color: vec4<f32>) -> vec4<f32> {
+    if manual_gamma {
+        return vec4<f32>(
+            linear_to_srgb(color.r),
+            linear_to_srgb(color.g),
+            linear_to_srgb(color.b),
+            color.a,
+        );
+    }
+
+    return color;
+}
+
 fn unpack_color(color: u32) -> vec4<f32> {
     return vec4<f32>(
         srgb_to_linear(f32((color & 0xff0000u) >> 16u) / 255.0),
@@ -65,9 +94,8 @@ fn vertex(
     let v = vertex.vertex_idx % 4u;
     let size = textureDimensions(tex);
     let fsize = vec2<f32> (f32(size.x), f32(size.y));
-    let total_tiles = u32(size.x / u32(vertex.tilesize));
-    let tileposx = f32(vertex.tile_id % total_tiles) * vertex.tilesize;
-    let tileposy = f32(vertex.tile_id / total_tiles) * vertex.tilesize;
+    let tileposx = f32(vertex.tile_pos.x);
+    let tileposy = f32(vertex.tile_pos.y);
 
     switch v {
         case 1u: {
@@ -122,31 +150,46 @@ fn vertex(
 
     result.color = unpack_color(vertex.color);
 
-    let id = global.seconds / (f32(250) / 1000.0);
-    let frame = u32(floor(id % f32(4)));
-
-    if pos.z == 9.3 && frame != 0u {
-        result.uv = vec2<f32>(0.0, 0.0);
-        result.clip_position = vec4<f32>(0.0, 0.0, 0.0, 0.0);
-    } else if pos.z == 9.2 && frame != 1u {
-        result.uv = vec2<f32>(0.0, 0.0);
-        result.clip_position = vec4<f32>(0.0, 0.0, 0.0, 0.0);
-    } else if pos.z == 9.1 && frame != 2u {
-        result.uv = vec2<f32>(0.0, 0.0);
-        result.clip_position = vec4<f32>(0.0, 0.0, 0.0, 0.0);
-    } else if pos.z == 9.0 && frame != 3u {
-        result.uv = vec2<f32>(0.0, 0.0);
-        result.clip_position = vec4<f32>(0.0, 0.0, 0.0, 0.0);
+    // Layers of the four frame layer animation are only shown in their frame.
+    if vertex.layer_frame > 0u {
+        let id = global.seconds / (f32(250) / 1000.0);
+        let frame = u32(floor(id % f32(4)));
+
+        if frame != vertex.layer_frame - 1u {
+            result.uv = vec2<f32>(0.0, 0.0);
+            result.clip_position = vec4<f32>(0.0, 0.0, 0.0, 0.0);
+        }
+    }
+
+    // Animated tiles get one instance per frame. Only the current one is shown.
+    if vertex.frames > 1u && vertex.time > 0u {
+        let step = global.seconds / (f32(vertex.time) / 1000.0);
+        let tile_frame = u32(floor(step % f32(vertex.frames)));
+
+        if tile_frame != vertex.frame {
+            result.uv = vec2<f32>(0.0, 0.0);
+            result.clip_position = vec4<f32>(0.0, 0.0, 0.0, 0.0);
+        }
     }
 
     result.uv_layer = i32(vertex.texture_layer);
     return result;
 }
 
+// Picks the mip level from how many texels one screen pixel covers.
+// Clamped to 0 by the sampler when the texture is not mipmapped.
+fn mip_level(texel_coords: vec2<f32>) -> f32 {
+    let dx = dpdx(texel_coords);
+    let dy = dpdy(texel_coords);
+
+    return max(0.0, 0.5 * log2(max(dot(dx, dx), dot(dy, dy))));
+}
+
 // Fragment shader
 @fragment
 fn fragment(vertex: VertexOutput,) -> @location(0) vec4<f32> {
-    let object_color = textureSampleLevel(tex, tex_sample, vertex.uv, vertex.uv_layer, 1.0);
+    let level = mip_level(vertex.uv * vec2<f32>(textureDimensions(tex)));
+    let object_color = textureSampleLevel(tex, tex_sample, vertex.uv, vertex.uv_layer, level);
 
     let color = object_color * vertex.color;
 
@@ -154,6 +197,6 @@ fn fragment(vertex: VertexOutput,) -> @location(0) vec4<f32> {
         discard;
     }
 
-    return color;
+    return output_color(color);
 }
 
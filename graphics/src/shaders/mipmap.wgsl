@@ -0,0 +1,37 @@
+// Downsamples one mip level of an atlas layer into the next.
+struct Params {
+    layer: u32,
+    padding0: u32,
+    padding1: u32,
+    padding2: u32,
+};
+
+@group(0) @binding(0)
+var source: texture_2d_array<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> params: Params;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+};
+
+@vertex
+fn vertex(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var result: VertexOutput;
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+
+    result.clip_position = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    return result;
+}
+
+// Sampling at the corner between four texels of the larger level with
+// linear filtering averages all four.
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let size = vec2<f32>(textureDimensions(source));
+    let uv = in.clip_position.xy * 2.0 / size;
+
+    return textureSampleLevel(source, source_sampler, uv, i32(params.layer), 0.0);
+}
@@ -0,0 +1,7 @@
+// input_tex: blurred highlights, second_tex: scene. data0.x: intensity.
+fn fragment(in: VertexOutput) -> vec4<f32> {
+    let bloom = sample_input(in.uv);
+    let scene = sample_second(in.uv);
+
+    return vec4<f32>(scene.rgb + bloom.rgb * params.data0.x, scene.a);
+}
@@ -0,0 +1,9 @@
+// data0.x: brightness threshold, data0.y: soft knee.
+fn fragment(in: VertexOutput) -> vec4<f32> {
+    let color = sample_input(in.uv);
+    let brightness = max(color.r, max(color.g, color.b));
+    let knee = max(params.data0.y, 0.0001);
+    let factor = clamp((brightness - params.data0.x) / knee, 0.0, 1.0);
+
+    return vec4<f32>(color.rgb * factor, 1.0);
+}
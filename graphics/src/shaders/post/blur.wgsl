@@ -0,0 +1,20 @@
+// data0.xy: direction, data0.z: radius in pixels.
+fn fragment(in: VertexOutput) -> vec4<f32> {
+    let texel = 1.0 / vec2<f32>(textureDimensions(input_tex));
+    let direction = params.data0.xy * texel;
+    let radius = clamp(i32(ceil(params.data0.z)), 0, 32);
+    let sigma = max(params.data0.z * 0.5, 0.0001);
+
+    var color = vec4<f32>(0.0);
+    var total = 0.0;
+
+    for (var i = -radius; i <= radius; i += 1) {
+        let offset = f32(i);
+        let weight = exp(-(offset * offset) / (2.0 * sigma * sigma));
+
+        color += sample_input(in.uv + direction * offset) * weight;
+        total += weight;
+    }
+
+    return color / total;
+}
@@ -0,0 +1,32 @@
+// second_tex: strip LUT of size * size by size holding sRGB values. data0.x: size,
+// data0.y: intensity.
+fn lut_sample(blue_slice: f32, rg: vec2<f32>, size: f32) -> vec3<f32> {
+    let x = (blue_slice * size + rg.x * (size - 1.0) + 0.5) / (size * size);
+    let y = (rg.y * (size - 1.0) + 0.5) / size;
+    return textureSampleLevel(
+        second_tex,
+        input_sampler,
+        vec2<f32>(x, y),
+        0,
+        0.0,
+    ).rgb;
+}
+
+fn fragment(in: VertexOutput) -> vec4<f32> {
+    let scene = sample_input(in.uv);
+    let size = params.data0.x;
+    let color = clamp(linear_to_srgb(scene.rgb), vec3<f32>(0.0), vec3<f32>(1.0));
+    let blue = color.b * (size - 1.0);
+    let low = floor(blue);
+    let high = min(low + 1.0, size - 1.0);
+    let graded = mix(
+        lut_sample(low, color.rg, size),
+        lut_sample(high, color.rg, size),
+        blue - low,
+    );
+
+    return vec4<f32>(
+        mix(scene.rgb, srgb_to_linear(graded), params.data0.y),
+        scene.a,
+    );
+}
@@ -0,0 +1,93 @@
+// Shared header of every post process shader.
+// Passes provide `fn fragment(in: VertexOutput) -> vec4<f32>` returning a
+// linear color which `post_fragment` gamma encodes when needed.
+
+struct PostParams {
+    data0: vec4<f32>,
+    data1: vec4<f32>,
+    data2: vec4<f32>,
+    data3: vec4<f32>,
+};
+
+@group(0)
+@binding(0)
+var input_tex: texture_2d_array<f32>;
+@group(0)
+@binding(1)
+var input_sampler: sampler;
+@group(0)
+@binding(2)
+var<uniform> params: PostParams;
+@group(0)
+@binding(3)
+var second_tex: texture_2d_array<f32>;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// Draws a single triangle covering the whole screen.
+@vertex
+fn vertex(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var result: VertexOutput;
+    let pos = vec2<f32>(
+        f32((vertex_index << 1u) & 2u) * 2.0 - 1.0,
+        f32(vertex_index & 2u) * 2.0 - 1.0,
+    );
+
+    result.clip_position = vec4<f32>(pos, 0.0, 1.0);
+    result.uv = vec2<f32>(pos.x * 0.5 + 0.5, 0.5 - pos.y * 0.5);
+    return result;
+}
+
+// Set by the Renderer when the surface is not sRGB so colors get gamma
+// encoded by hand.
+override manual_gamma: bool = false;
+
+// Decodes a color read from a target when it was gamma encoded by hand.
+fn decode_color(color: vec4<f32>) -> vec4<f32> {
+    if manual_gamma {
+        return vec4<f32>(srgb_to_linear(color.rgb), color.a);
+    }
+
+    return color;
+}
+
+// Samples the pass input at uv as a linear color.
+fn sample_input(uv: vec2<f32>) -> vec4<f32> {
+    return decode_color(
+        textureSampleLevel(input_tex, input_sampler, uv, 0, 0.0),
+    );
+}
+
+// Samples the secondary texture (scene) at uv as a linear color.
+fn sample_second(uv: vec2<f32>) -> vec4<f32> {
+    return decode_color(
+        textureSampleLevel(second_tex, input_sampler, uv, 0, 0.0),
+    );
+}
+
+@fragment
+fn post_fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = fragment(in);
+
+    if manual_gamma {
+        return vec4<f32>(linear_to_srgb(color.rgb), color.a);
+    }
+
+    return color;
+}
+
+fn linear_to_srgb(c: vec3<f32>) -> vec3<f32> {
+    let low = c * 12.92;
+    let high = 1.055 * pow(max(c, vec3<f32>(0.0)), vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, c <= vec3<f32>(0.0031308));
+}
+
+fn srgb_to_linear(c: vec3<f32>) -> vec3<f32> {
+    let low = c / 12.92;
+    let high = pow((max(c, vec3<f32>(0.0)) + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, c <= vec3<f32>(0.04045));
+}
+
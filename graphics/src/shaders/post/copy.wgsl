@@ -0,0 +1,3 @@
+fn fragment(in: VertexOutput) -> vec4<f32> {
+    return sample_input(in.uv);
+}
@@ -0,0 +1,7 @@
+// data0: color, data1.x: amount.
+fn fragment(in: VertexOutput) -> vec4<f32> {
+    let scene = sample_input(in.uv);
+    let amount = clamp(params.data1.x * params.data0.a, 0.0, 1.0);
+
+    return vec4<f32>(mix(scene.rgb, params.data0.rgb, amount), scene.a);
+}
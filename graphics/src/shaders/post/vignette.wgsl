@@ -0,0 +1,12 @@
+// data0: color, data1.x: radius, data1.y: softness, data1.z: intensity.
+fn fragment(in: VertexOutput) -> vec4<f32> {
+    let scene = sample_input(in.uv);
+    let dist = distance(in.uv, vec2<f32>(0.5)) * 1.41421356;
+    let edge = smoothstep(
+        params.data1.x,
+        params.data1.x + max(params.data1.y, 0.0001),
+        dist,
+    ) * params.data1.z * params.data0.a;
+
+    return vec4<f32>(mix(scene.rgb, params.data0.rgb, edge), scene.a);
+}
@@ -66,6 +66,31 @@ fn srgb_to_linear(c: f32) -> f32 {
     }
 }
 
+// Set by the Renderer when the surface is not sRGB so colors get gamma
+// encoded by hand.
+override manual_gamma: bool = false;
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        return c * 12.92;
+    } else {
+        return 1.055 * pow(c, 1.0 / 2.4) - 0.055;
+    }
+}
+
+fn output_color(color: vec4<f32>) -> vec4<f32> {
+    if manual_gamma {
+        return vec4<f32>(
+            linear_to_srgb(color.r),
+            linear_to_srgb(color.g),
+            linear_to_srgb(color.b),
+            color.a,
+        );
+    }
+
+    return color;
+}
+
 fn unpack_color(color: u32) -> vec4<f32> {
     return vec4<f32>(
         srgb_to_linear(f32((color & 0xff0000u) >> 16u) / 255.0),
@@ -178,13 +203,23 @@ fn distance_alg(
     return sqrt(dist.x * dist.x + dist.y * dist.y);
 }
 
+// Picks the mip level from how many texels one screen pixel covers.
+// Clamped to 0 by the sampler when the texture is not mipmapped.
+fn mip_level(texel_coords: vec2<f32>) -> f32 {
+    let dx = dpdx(texel_coords);
+    let dy = dpdy(texel_coords);
+
+    return max(0.0, 0.5 * log2(max(dot(dx, dx), dot(dy, dy))));
+}
+
 @fragment
 fn fragment(vertex: VertexOutput,) -> @location(0) vec4<f32> {
     let coords = (vertex.container_data.xy + vertex.uv.xy) / vertex.tex_size;
+    let level = mip_level(vertex.container_data.xy + vertex.uv.xy);
 
     let c1 = select(
         vec4<f32>(0.0), 
-        textureSampleLevel(tex, tex_sample, coords, vertex.layer, 1.0),
+        textureSampleLevel(tex, tex_sample, coords, vertex.layer, level),
         vertex.container_data[2] > 0.0 && vertex.container_data[3] > 0.0
     );
     let container_color = select(vertex.color, c1  * vertex.color, vertex.container_data[2] > 0.0 && vertex.container_data[3] > 0.0);
@@ -223,5 +258,5 @@ fn fragment(vertex: VertexOutput,) -> @location(0) vec4<f32> {
         discard;
     }
 
-    return vec4<f32>(mixed_color.r, mixed_color.g, mixed_color.b, alpha);
+    return output_color(vec4<f32>(mixed_color.r, mixed_color.g, mixed_color.b, alpha));
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 mod bounds;
 mod buffer;
+mod capture;
 mod device;
 mod draw_order;
 mod instance_buffer;
@@ -8,6 +9,7 @@ mod pass;
 mod pipelines;
 mod renderer;
 mod static_vbo;
+mod surface_format;
 mod system;
 mod vbo;
 
@@ -15,6 +17,7 @@ pub use bounds::Bounds;
 pub use buffer::{
     AsBufferPass, Buffer, BufferData, BufferLayout, BufferPass, BufferStore,
 };
+pub use capture::encode_png;
 pub use device::*;
 pub use draw_order::{DrawOrder, Index, OrderedIndex};
 pub use instance_buffer::*;
@@ -24,6 +27,8 @@ pub use pipelines::*;
 pub use renderer::*;
 pub use slotmap::KeyData;
 pub use static_vbo::*;
+pub(crate) use surface_format::gamma_constants;
+pub use surface_format::{SurfaceFormatPolicy, needs_manual_gamma};
 pub use system::*;
 pub use vbo::*;
 
@@ -52,3 +57,69 @@ pub enum FlipStyle {
     Vertical,
     Both,
 }
+
+/// How a Renderer blends what it draws with what is already rendered.
+/// Part of each Renderers Pipeline key so every mode gets its own Pipeline.
+///
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// Standard non premultiplied alpha blending.
+    #[default]
+    Alpha,
+    /// Adds the alpha scaled color onto the target. Used for glows.
+    Additive,
+    /// Multiplies the target by the color. Used for shadows.
+    /// Transparent areas should be white as alpha is not applied.
+    Multiply,
+    /// Alpha blending for colors already multiplied by their alpha.
+    PremultipliedAlpha,
+}
+
+impl BlendMode {
+    /// Returns the [`wgpu::BlendState`] of the mode.
+    ///
+    pub fn to_blend_state(self) -> wgpu::BlendState {
+        match self {
+            BlendMode::Alpha => wgpu::BlendState::ALPHA_BLENDING,
+            BlendMode::Additive => wgpu::BlendState {
+                color: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::SrcAlpha,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::Zero,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+            },
+            BlendMode::Multiply => wgpu::BlendState {
+                color: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::Dst,
+                    dst_factor: wgpu::BlendFactor::Zero,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::Zero,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+            },
+            BlendMode::PremultipliedAlpha => {
+                wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING
+            }
+        }
+    }
+
+    /// Converts the value stored in a Pipeline key back into a [`BlendMode`].
+    ///
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => BlendMode::Additive,
+            2 => BlendMode::Multiply,
+            3 => BlendMode::PremultipliedAlpha,
+            _ => BlendMode::Alpha,
+        }
+    }
+}
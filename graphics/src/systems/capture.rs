@@ -0,0 +1,247 @@
+use crate::{GpuRenderer, GraphicsError, OtherError};
+use image::{ImageFormat, RgbaImage};
+use std::{io::Cursor, path::Path, sync::mpsc};
+
+impl GpuRenderer {
+    /// Returns a reference to the [`wgpu::Texture`] currently being rendered too.
+    /// This is the Surfaces Texture after [`GpuRenderer::update`] or the
+    /// offscreen Texture when Headless.
+    ///
+    pub fn frame_texture(&self) -> Option<&wgpu::Texture> {
+        match &self.frame {
+            Some(frame) => Some(&frame.texture),
+            None => self.window.offscreen_texture(),
+        }
+    }
+
+    /// Copies the current frame into an [`RgbaImage`].
+    /// Must be called after rendering was submitted but before
+    /// [`GpuRenderer::present`] when rendering to a Window.
+    ///
+    pub fn capture_frame(&self) -> Result<RgbaImage, GraphicsError> {
+        let texture = self.frame_texture().ok_or_else(|| {
+            OtherError::new(
+                "Frame does not Exist. Did you forget to update the renderer?",
+            )
+        })?;
+
+        self.capture_texture(texture)
+    }
+
+    /// Copies the current frame and encodes it as PNG bytes.
+    ///
+    pub fn capture_frame_png(&self) -> Result<Vec<u8>, GraphicsError> {
+        encode_png(&self.capture_frame()?)
+    }
+
+    /// Copies the current frame and saves it as a PNG file at path.
+    ///
+    pub fn save_frame_png(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> Result<(), GraphicsError> {
+        self.capture_frame()?
+            .save_with_format(path, ImageFormat::Png)?;
+        Ok(())
+    }
+
+    /// Copies the first layer of a [`wgpu::Texture`] into an [`RgbaImage`].
+    /// The texture must have been created with [`wgpu::TextureUsages::COPY_SRC`].
+    ///
+    pub fn capture_texture(
+        &self,
+        texture: &wgpu::Texture,
+    ) -> Result<RgbaImage, GraphicsError> {
+        self.capture_texture_layer(texture, 0)
+    }
+
+    /// Copies a array layer of a [`wgpu::Texture`] into an [`RgbaImage`].
+    /// The texture must have been created with [`wgpu::TextureUsages::COPY_SRC`].
+    ///
+    /// Supports Rgba8, Bgra8, R8 and Rgba16Float formats. Float formats are
+    /// converted from linear into sRGB.
+    ///
+    pub fn capture_texture_layer(
+        &self,
+        texture: &wgpu::Texture,
+        layer: u32,
+    ) -> Result<RgbaImage, GraphicsError> {
+        let format = texture.format();
+        let (width, height) = (texture.width(), texture.height());
+
+        if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+            return Err(GraphicsError::Other(OtherError::new(
+                "Texture was not created with TextureUsages::COPY_SRC.",
+            )));
+        }
+
+        if layer >= texture.depth_or_array_layers() {
+            return Err(GraphicsError::Other(OtherError::new(
+                "Texture layer is out of range.",
+            )));
+        }
+
+        let bytes_per_pixel = match format {
+            wgpu::TextureFormat::Rgba8Unorm
+            | wgpu::TextureFormat::Rgba8UnormSrgb
+            | wgpu::TextureFormat::Bgra8Unorm
+            | wgpu::TextureFormat::Bgra8UnormSrgb => 4,
+            wgpu::TextureFormat::R8Unorm => 1,
+            wgpu::TextureFormat::Rgba16Float => 8,
+            _ => return Err(GraphicsError::UnsupportedFormat(format)),
+        };
+
+        let unpadded_bytes_per_row = width * bytes_per_pixel;
+        let padded_bytes_per_row = unpadded_bytes_per_row
+            .div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let buffer = self.device().create_buffer(&wgpu::BufferDescriptor {
+            label: Some("capture buffer"),
+            size: (padded_bytes_per_row * height) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self.device().create_command_encoder(
+            &wgpu::CommandEncoderDescriptor {
+                label: Some("capture command encoder"),
+            },
+        );
+
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: 0,
+                    y: 0,
+                    z: layer,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        self.queue().submit(std::iter::once(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let (sender, receiver) = mpsc::channel();
+
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+
+        self.device().poll(wgpu::PollType::Wait)?;
+        receiver.recv().map_err(|_| {
+            OtherError::new("Capture buffer mapping was never completed.")
+        })??;
+
+        let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+
+        {
+            let data = slice.get_mapped_range();
+
+            for row in data.chunks(padded_bytes_per_row as usize) {
+                convert_row(
+                    format,
+                    &row[..unpadded_bytes_per_row as usize],
+                    &mut pixels,
+                );
+            }
+        }
+
+        buffer.unmap();
+
+        RgbaImage::from_raw(width, height, pixels).ok_or_else(|| {
+            GraphicsError::Other(OtherError::new(
+                "Captured data did not match the texture size.",
+            ))
+        })
+    }
+}
+
+/// Encodes an [`RgbaImage`] as PNG bytes.
+///
+pub fn encode_png(image: &RgbaImage) -> Result<Vec<u8>, GraphicsError> {
+    let mut bytes = Cursor::new(Vec::new());
+
+    image.write_to(&mut bytes, ImageFormat::Png)?;
+    Ok(bytes.into_inner())
+}
+
+/// Converts one row of texture data into Rgba8 sRGB pixels.
+///
+fn convert_row(format: wgpu::TextureFormat, row: &[u8], pixels: &mut Vec<u8>) {
+    match format {
+        wgpu::TextureFormat::Bgra8Unorm
+        | wgpu::TextureFormat::Bgra8UnormSrgb => {
+            for bgra in row.chunks_exact(4) {
+                pixels.extend_from_slice(&[bgra[2], bgra[1], bgra[0], bgra[3]]);
+            }
+        }
+        wgpu::TextureFormat::R8Unorm => {
+            for &value in row {
+                pixels.extend_from_slice(&[value, value, value, 255]);
+            }
+        }
+        wgpu::TextureFormat::Rgba16Float => {
+            for rgba in row.chunks_exact(8) {
+                let channel = |i: usize| {
+                    f16_to_f32(u16::from_le_bytes([
+                        rgba[i * 2],
+                        rgba[i * 2 + 1],
+                    ]))
+                };
+
+                pixels.extend_from_slice(&[
+                    linear_to_srgb(channel(0)),
+                    linear_to_srgb(channel(1)),
+                    linear_to_srgb(channel(2)),
+                    (channel(3).clamp(0.0, 1.0) * 255.0).round() as u8,
+                ]);
+            }
+        }
+        _ => pixels.extend_from_slice(row),
+    }
+}
+
+/// Converts a linear color channel into a 8 bit sRGB value.
+///
+fn linear_to_srgb(c: f32) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let srgb = if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+
+    (srgb * 255.0).round() as u8
+}
+
+/// Converts the bits of a half precision float into a f32.
+///
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{GpuRenderer, GraphicsError};
+use crate::{GpuRenderer, GraphicsError, SurfaceFormatPolicy};
 use async_trait::async_trait;
 use log::debug;
 #[cfg(feature = "rayon")]
@@ -11,9 +11,58 @@ use winit::{dpi::PhysicalSize, event::WindowEvent, window::Window};
 pub struct GpuDevice {
     pub device: wgpu::Device,
     pub queue: wgpu::Queue,
+    /// Multisample count Pipelines and Render Attachments are created with.
+    pub(crate) sample_count: u32,
 }
 
 impl GpuDevice {
+    /// Creates a [`GpuDevice`] without Multisampling.
+    ///
+    pub fn new(device: wgpu::Device, queue: wgpu::Queue) -> Self {
+        Self {
+            device,
+            queue,
+            sample_count: 1,
+        }
+    }
+
+    /// Returns the Multisample count used by the Renderers Pipelines.
+    ///
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+
+    /// Returns the [`wgpu::MultisampleState`] Pipelines must be created with
+    /// to render into the Renderers Attachments.
+    ///
+    pub fn multisample_state(&self) -> wgpu::MultisampleState {
+        wgpu::MultisampleState {
+            count: self.sample_count,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the features the Device allows for a Texture format. These
+    /// are the Adapter's when the Device enabled
+    /// [`wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`],
+    /// otherwise only the guaranteed ones.
+    ///
+    pub fn texture_format_features(
+        &self,
+        adapter: &wgpu::Adapter,
+        format: wgpu::TextureFormat,
+    ) -> wgpu::TextureFormatFeatures {
+        let features = self.device.features();
+
+        if features
+            .contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES)
+        {
+            adapter.get_texture_format_features(format)
+        } else {
+            format.guaranteed_format_features(features)
+        }
+    }
+
     pub fn device(&self) -> &wgpu::Device {
         &self.device
     }
@@ -48,17 +97,45 @@ pub struct AdapterOptions {
     pub compatible_surface: Option<Surface<'static>>,
 }
 
+/// Surface Presentation settings that can be changed at runtime using
+/// [`GpuRenderer::reconfigure_surface`].
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SurfaceSettings {
+    /// How frames are presented. Fifo is VSync on and Immediate is VSync off.
+    pub present_mode: wgpu::PresentMode,
+    /// How the Window blends with what is behind it.
+    pub alpha_mode: wgpu::CompositeAlphaMode,
+    /// Amount of frames that may be queued up. Must be at least 1.
+    pub desired_maximum_frame_latency: u32,
+}
+
+impl Default for SurfaceSettings {
+    fn default() -> Self {
+        Self {
+            present_mode: wgpu::PresentMode::Fifo,
+            alpha_mode: wgpu::CompositeAlphaMode::Auto,
+            desired_maximum_frame_latency: 2,
+        }
+    }
+}
+
 /// Handles the [`wgpu::Adapter`], [`wgpu::Surface`], [`Window`].
 /// Also used to Keep track of [`wgpu::TextureFormat`], [`wgpu::SurfaceConfiguration`]
 /// and [`Window`] Sizes.
 ///
+/// When created Headless there is no [`Window`] or [`wgpu::Surface`] and
+/// everything is rendered into an owned offscreen [`wgpu::Texture`] instead.
+///
 pub struct GpuWindow {
     /// GPU Adapter we will render from.
     pub(crate) adapter: wgpu::Adapter,
-    /// Window Surface we will Render Too.
-    pub(crate) surface: wgpu::Surface<'static>,
-    /// Window we are using to Render Too.
-    pub(crate) window: Arc<Window>,
+    /// Window Surface we will Render Too. None when Headless.
+    pub(crate) surface: Option<wgpu::Surface<'static>>,
+    /// Window we are using to Render Too. None when Headless.
+    pub(crate) window: Option<Arc<Window>>,
+    /// Offscreen Texture we Render Too when Headless.
+    pub(crate) offscreen: Option<wgpu::Texture>,
     /// Current Allowed surface_format of the GPU and Window.
     pub(crate) surface_format: wgpu::TextureFormat,
     /// Windows Overall Size.
@@ -89,23 +166,123 @@ impl GpuWindow {
 
         self.surface_config.height = size.height;
         self.surface_config.width = size.width;
-        self.surface
-            .configure(gpu_device.device(), &self.surface_config);
+
+        if let Some(surface) = &self.surface {
+            surface.configure(gpu_device.device(), &self.surface_config);
+        } else {
+            self.offscreen = Some(create_offscreen_texture(
+                gpu_device,
+                size,
+                self.surface_format,
+            ));
+            self.inner_size = size;
+        }
+
         self.size = PhysicalSize::new(size.width as f32, size.height as f32);
 
         Ok(())
     }
 
+    /// Returns a reference to the current [`wgpu::SurfaceConfiguration`].
+    ///
+    pub fn surface_config(&self) -> &wgpu::SurfaceConfiguration {
+        &self.surface_config
+    }
+
+    /// Returns the current [`SurfaceSettings`].
+    ///
+    pub fn surface_settings(&self) -> SurfaceSettings {
+        SurfaceSettings {
+            present_mode: self.surface_config.present_mode,
+            alpha_mode: self.surface_config.alpha_mode,
+            desired_maximum_frame_latency: self
+                .surface_config
+                .desired_maximum_frame_latency,
+        }
+    }
+
+    /// Returns the [`wgpu::SurfaceCapabilities`] if not Headless.
+    ///
+    pub fn surface_capabilities(&self) -> Option<wgpu::SurfaceCapabilities> {
+        self.surface
+            .as_ref()
+            .map(|surface| surface.get_capabilities(&self.adapter))
+    }
+
+    /// Validates the [`SurfaceSettings`] against the Surfaces capabilities
+    /// and reconfigures the [`wgpu::Surface`] with them.
+    /// When Headless the settings are only stored.
+    ///
+    pub fn reconfigure(
+        &mut self,
+        gpu_device: &GpuDevice,
+        settings: SurfaceSettings,
+    ) -> Result<(), GraphicsError> {
+        if settings.desired_maximum_frame_latency == 0 {
+            return Err(GraphicsError::UnsupportedFrameLatency(
+                settings.desired_maximum_frame_latency,
+            ));
+        }
+
+        if let Some(caps) = self.surface_capabilities() {
+            let present_mode_supported = matches!(
+                settings.present_mode,
+                wgpu::PresentMode::AutoVsync | wgpu::PresentMode::AutoNoVsync
+            ) || caps
+                .present_modes
+                .contains(&settings.present_mode);
+
+            if !present_mode_supported {
+                return Err(GraphicsError::UnsupportedPresentMode(
+                    settings.present_mode,
+                ));
+            }
+
+            if settings.alpha_mode != wgpu::CompositeAlphaMode::Auto
+                && !caps.alpha_modes.contains(&settings.alpha_mode)
+            {
+                return Err(GraphicsError::UnsupportedAlphaMode(
+                    settings.alpha_mode,
+                ));
+            }
+        }
+
+        self.surface_config.present_mode = settings.present_mode;
+        self.surface_config.alpha_mode = settings.alpha_mode;
+        self.surface_config.desired_maximum_frame_latency =
+            settings.desired_maximum_frame_latency;
+
+        if let Some(surface) = &self.surface {
+            if self.surface_config.width > 0 && self.surface_config.height > 0 {
+                surface.configure(gpu_device.device(), &self.surface_config);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Returns the Size of the [`wgpu::Surface`].
     ///
     pub fn size(&self) -> PhysicalSize<f32> {
         self.size
     }
 
-    /// Returns Reference to the [`wgpu::Surface`].
+    /// Returns Reference to the [`wgpu::Surface`] if not Headless.
+    ///
+    pub fn surface(&self) -> Option<&wgpu::Surface<'static>> {
+        self.surface.as_ref()
+    }
+
+    /// Returns Reference to the offscreen [`wgpu::Texture`] if Headless.
     ///
-    pub fn surface(&self) -> &wgpu::Surface {
-        &self.surface
+    pub fn offscreen_texture(&self) -> Option<&wgpu::Texture> {
+        self.offscreen.as_ref()
+    }
+
+    /// Returns true if there is no [`Window`] and we render offscreen.
+    ///
+    pub fn is_headless(&self) -> bool {
+        self.window.is_none()
     }
 
     /// Returns the [`wgpu::TextureFormat`].
@@ -115,16 +292,23 @@ impl GpuWindow {
     }
 
     /// Resizes the [`wgpu::Surface`] and/or requests a redraw event for the Window.
+    /// Does nothing when Headless as there are no Window events.
     ///
     pub fn update(
         &mut self,
         gpu_device: &GpuDevice,
         event: &WindowEvent,
     ) -> Result<Option<wgpu::SurfaceTexture>, GraphicsError> {
+        let (Some(window), Some(surface)) = (&self.window, &self.surface)
+        else {
+            return Ok(None);
+        };
+        let window = window.clone();
+
         match event {
             WindowEvent::Resized(physical_size) => {
                 self.resize(gpu_device, *physical_size)?;
-                self.inner_size = self.window.inner_size();
+                self.inner_size = window.inner_size();
 
                 if self.size.width == 0.0
                     || self.size.height == 0.0
@@ -134,7 +318,7 @@ impl GpuWindow {
                     return Ok(None);
                 }
 
-                self.window.request_redraw();
+                window.request_redraw();
             }
             WindowEvent::RedrawRequested => {
                 if self.size.width == 0.0
@@ -145,9 +329,9 @@ impl GpuWindow {
                     return Ok(None);
                 }
 
-                match self.surface.get_current_texture() {
+                match surface.get_current_texture() {
                     Ok(frame) => {
-                        self.window.request_redraw();
+                        window.request_redraw();
                         return Ok(Some(frame));
                     }
                     Err(wgpu::SurfaceError::Lost) => {
@@ -156,7 +340,7 @@ impl GpuWindow {
                             self.size.height as u32,
                         );
                         self.resize(gpu_device, size)?;
-                        self.inner_size = self.window.inner_size();
+                        self.inner_size = window.inner_size();
 
                         if self.size.width == 0.0
                             || self.size.height == 0.0
@@ -172,7 +356,7 @@ impl GpuWindow {
                     Err(e) => return Err(GraphicsError::from(e)),
                 }
 
-                self.window.request_redraw();
+                window.request_redraw();
             }
             WindowEvent::Moved(_)
             | WindowEvent::ScaleFactorChanged {
@@ -181,7 +365,7 @@ impl GpuWindow {
             }
             | WindowEvent::Focused(true)
             | WindowEvent::Occluded(false) => {
-                self.window.request_redraw();
+                window.request_redraw();
             }
             _ => (),
         }
@@ -189,10 +373,10 @@ impl GpuWindow {
         Ok(None)
     }
 
-    /// Returns a Reference to [`Window`].
+    /// Returns a Reference to [`Window`] if not Headless.
     ///
-    pub fn window(&self) -> &Window {
-        &self.window
+    pub fn window(&self) -> Option<&Window> {
+        self.window.as_deref()
     }
 
     /// Creates a Depth Texture from the [`GpuDevice`]
@@ -208,6 +392,16 @@ impl GpuWindow {
             depth_or_array_layers: 1,
         };
 
+        // Multisampled Depth can only be an Attachment. Keeping it the same
+        // kind of Attachment as the Color Buffer keeps Opengl happy.
+        let usage = if gpu_device.sample_count() > 1 {
+            wgpu::TextureUsages::RENDER_ATTACHMENT
+        } else {
+            wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_DST
+        };
+
         let texture =
             gpu_device
                 .device()
@@ -215,17 +409,74 @@ impl GpuWindow {
                     label: Some("depth texture"),
                     size,
                     mip_level_count: 1,
-                    sample_count: 1,
+                    sample_count: gpu_device.sample_count(),
                     dimension: wgpu::TextureDimension::D2,
                     format: wgpu::TextureFormat::Depth32Float,
-                    usage: wgpu::TextureUsages::TEXTURE_BINDING
-                        | wgpu::TextureUsages::RENDER_ATTACHMENT
-                        | wgpu::TextureUsages::COPY_DST,
+                    usage,
                     view_formats: &[TextureFormat::Depth32Float],
                 });
 
         texture.create_view(&wgpu::TextureViewDescriptor::default())
     }
+
+    /// Creates the Multisampled Color Texture that gets resolved into the
+    /// frame. Returns None when the [`GpuDevice`] does not use Multisampling.
+    ///
+    pub fn create_msaa_texture(
+        &self,
+        gpu_device: &GpuDevice,
+    ) -> Option<wgpu::TextureView> {
+        if gpu_device.sample_count() <= 1 {
+            return None;
+        }
+
+        let texture =
+            gpu_device
+                .device()
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some("msaa texture"),
+                    size: wgpu::Extent3d {
+                        width: (self.size.width as u32).max(1),
+                        height: (self.size.height as u32).max(1),
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: gpu_device.sample_count(),
+                    dimension: wgpu::TextureDimension::D2,
+                    format: self.surface_format,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: &[self.surface_format],
+                });
+
+        Some(texture.create_view(&wgpu::TextureViewDescriptor::default()))
+    }
+}
+
+/// Creates the Texture a Headless [`GpuWindow`] Renders into.
+///
+fn create_offscreen_texture(
+    gpu_device: &GpuDevice,
+    size: PhysicalSize<u32>,
+    format: wgpu::TextureFormat,
+) -> wgpu::Texture {
+    gpu_device
+        .device()
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("offscreen texture"),
+            size: wgpu::Extent3d {
+                width: size.width.max(1),
+                height: size.height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[format],
+        })
 }
 
 /// Trait used to Allow the [`wgpu::Adapter`] to Create a [`GpuRenderer`].
@@ -241,6 +492,28 @@ pub trait AdapterExt {
         device_descriptor: &wgpu::DeviceDescriptor,
         present_mode: wgpu::PresentMode,
     ) -> Result<GpuRenderer, GraphicsError>;
+
+    /// Creates a [`GpuRenderer`] whose Surface format gets picked using
+    /// the [`SurfaceFormatPolicy`].
+    ///
+    async fn create_renderer_with_policy(
+        self,
+        instance: &wgpu::Instance,
+        window: &Arc<Window>,
+        device_descriptor: &wgpu::DeviceDescriptor,
+        present_mode: wgpu::PresentMode,
+        format_policy: SurfaceFormatPolicy,
+    ) -> Result<GpuRenderer, GraphicsError>;
+
+    /// Creates a Headless [`GpuRenderer`] that renders into an offscreen
+    /// Texture of size and format rather than a [`Window`]'s Surface.
+    ///
+    async fn create_headless_renderer(
+        self,
+        device_descriptor: &wgpu::DeviceDescriptor,
+        size: PhysicalSize<u32>,
+        format: wgpu::TextureFormat,
+    ) -> Result<GpuRenderer, GraphicsError>;
 }
 
 #[async_trait]
@@ -251,53 +524,41 @@ impl AdapterExt for wgpu::Adapter {
         window: &Arc<Window>,
         device_descriptor: &wgpu::DeviceDescriptor,
         present_mode: wgpu::PresentMode,
+    ) -> Result<GpuRenderer, GraphicsError> {
+        self.create_renderer_with_policy(
+            instance,
+            window,
+            device_descriptor,
+            present_mode,
+            SurfaceFormatPolicy::default(),
+        )
+        .await
+    }
+
+    async fn create_renderer_with_policy(
+        self,
+        instance: &wgpu::Instance,
+        window: &Arc<Window>,
+        device_descriptor: &wgpu::DeviceDescriptor,
+        present_mode: wgpu::PresentMode,
+        format_policy: SurfaceFormatPolicy,
     ) -> Result<GpuRenderer, GraphicsError> {
         let size = window.inner_size();
 
         let (device, queue) = self.request_device(device_descriptor).await?;
 
-        let surface = instance.create_surface(window.clone()).unwrap();
+        let surface = instance.create_surface(window.clone())?;
         let caps = surface.get_capabilities(&self);
 
         debug!("{:?}", caps.formats);
 
-        #[cfg(feature = "rayon")]
-        let rgba = caps
-            .formats
-            .par_iter()
-            .find_first(|&&v| v == TextureFormat::Rgba8UnormSrgb);
-
-        #[cfg(feature = "rayon")]
-        let bgra = caps
-            .formats
-            .par_iter()
-            .find_first(|&&v| v == TextureFormat::Bgra8UnormSrgb);
-
-        #[cfg(not(feature = "rayon"))]
-        let rgba = caps
-            .formats
-            .iter()
-            .find(|&&v| v == TextureFormat::Rgba8UnormSrgb);
-
-        #[cfg(not(feature = "rayon"))]
-        let bgra = caps
-            .formats
-            .iter()
-            .find(|&&v| v == TextureFormat::Bgra8UnormSrgb);
-
-        let format = if rgba.is_some() {
-            TextureFormat::Rgba8UnormSrgb
-        } else if bgra.is_some() {
-            TextureFormat::Bgra8UnormSrgb
-        } else {
-            panic!(
-                "Your Rendering Device does not support Bgra8UnormSrgb or Rgba8UnormSrgb"
-            );
-        };
+        let format = format_policy.select(&caps.formats)?;
 
         debug!("surface format: {:?}", format);
         let surface_config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            // COPY_SRC is used for Screenshots if the Surface supports it.
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | (caps.usages & wgpu::TextureUsages::COPY_SRC),
             format,
             width: size.width,
             height: size.height,
@@ -312,14 +573,66 @@ impl AdapterExt for wgpu::Adapter {
         let mut renderer = GpuRenderer::new(
             GpuWindow {
                 adapter: self,
-                surface,
-                window: window.clone(),
+                surface: Some(surface),
+                window: Some(window.clone()),
+                offscreen: None,
                 surface_format: format,
                 size: PhysicalSize::new(size.width as f32, size.height as f32),
                 surface_config,
                 inner_size,
             },
-            GpuDevice { device, queue },
+            GpuDevice::new(device, queue),
+        );
+
+        // Creates the shader rendering pipelines for each renderer.
+        renderer.create_pipelines(renderer.surface_format());
+        Ok(renderer)
+    }
+
+    async fn create_headless_renderer(
+        self,
+        device_descriptor: &wgpu::DeviceDescriptor,
+        size: PhysicalSize<u32>,
+        format: wgpu::TextureFormat,
+    ) -> Result<GpuRenderer, GraphicsError> {
+        let (device, queue) = self.request_device(device_descriptor).await?;
+        let gpu_device = GpuDevice::new(device, queue);
+
+        if !gpu_device
+            .texture_format_features(&self, format)
+            .allowed_usages
+            .contains(wgpu::TextureUsages::RENDER_ATTACHMENT)
+        {
+            return Err(GraphicsError::UnsupportedFormat(format));
+        }
+
+        let size = PhysicalSize::new(size.width.max(1), size.height.max(1));
+
+        debug!("headless format: {:?}", format);
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format,
+            width: size.width,
+            height: size.height,
+            present_mode: wgpu::PresentMode::Fifo,
+            alpha_mode: wgpu::CompositeAlphaMode::Auto,
+            view_formats: vec![format],
+            desired_maximum_frame_latency: 2,
+        };
+
+        let offscreen = create_offscreen_texture(&gpu_device, size, format);
+        let mut renderer = GpuRenderer::new(
+            GpuWindow {
+                adapter: self,
+                surface: None,
+                window: None,
+                offscreen: Some(offscreen),
+                surface_format: format,
+                size: PhysicalSize::new(size.width as f32, size.height as f32),
+                surface_config,
+                inner_size: size,
+            },
+            gpu_device,
         );
 
         // Creates the shader rendering pipelines for each renderer.
@@ -343,6 +656,30 @@ pub trait InstanceExt {
         present_mode: wgpu::PresentMode,
     ) -> Result<GpuRenderer, GraphicsError>;
 
+    /// Creates a [`GpuRenderer`] whose Surface format gets picked using
+    /// the [`SurfaceFormatPolicy`].
+    ///
+    async fn create_device_with_policy(
+        &self,
+        window: Arc<Window>,
+        options: AdapterOptions,
+        device_descriptor: &wgpu::DeviceDescriptor,
+        present_mode: wgpu::PresentMode,
+        format_policy: SurfaceFormatPolicy,
+    ) -> Result<GpuRenderer, GraphicsError>;
+
+    /// Creates a Headless [`GpuRenderer`] that renders into an offscreen Texture.
+    /// Set [`AdapterOptions::compatible_surface`] to None and allow
+    /// [`Backends`] with software adapters for use in CI.
+    ///
+    async fn create_headless_device(
+        &self,
+        options: AdapterOptions,
+        device_descriptor: &wgpu::DeviceDescriptor,
+        size: PhysicalSize<u32>,
+        format: wgpu::TextureFormat,
+    ) -> Result<GpuRenderer, GraphicsError>;
+
     /// Gets a list of Avaliable Adapters based upon the [`AdapterOptions`].
     ///
     fn get_adapters(&self, options: AdapterOptions)
@@ -442,13 +779,37 @@ impl InstanceExt for wgpu::Instance {
         options: AdapterOptions,
         device_descriptor: &wgpu::DeviceDescriptor,
         present_mode: wgpu::PresentMode,
+    ) -> Result<GpuRenderer, GraphicsError> {
+        self.create_device_with_policy(
+            window,
+            options,
+            device_descriptor,
+            present_mode,
+            SurfaceFormatPolicy::default(),
+        )
+        .await
+    }
+
+    async fn create_device_with_policy(
+        &self,
+        window: Arc<Window>,
+        options: AdapterOptions,
+        device_descriptor: &wgpu::DeviceDescriptor,
+        present_mode: wgpu::PresentMode,
+        format_policy: SurfaceFormatPolicy,
     ) -> Result<GpuRenderer, GraphicsError> {
         let mut adapters = self.get_adapters(options);
 
         while let Some(adapter) = adapters.pop() {
             let ret = adapter
                 .0
-                .create_renderer(self, &window, device_descriptor, present_mode)
+                .create_renderer_with_policy(
+                    self,
+                    &window,
+                    device_descriptor,
+                    present_mode,
+                    format_policy,
+                )
                 .await;
 
             if ret.is_ok() {
@@ -473,4 +834,31 @@ impl InstanceExt for wgpu::Instance {
 
         Err(GraphicsError::AdapterNotFound)
     }
+
+    async fn create_headless_device(
+        &self,
+        options: AdapterOptions,
+        device_descriptor: &wgpu::DeviceDescriptor,
+        size: PhysicalSize<u32>,
+        format: wgpu::TextureFormat,
+    ) -> Result<GpuRenderer, GraphicsError> {
+        let mut adapters = self.get_adapters(options);
+
+        while let Some(adapter) = adapters.pop() {
+            let ret = adapter
+                .0
+                .create_headless_renderer(device_descriptor, size, format)
+                .await;
+
+            if ret.is_ok() {
+                if adapter.1 == 5 {
+                    debug!("A Software rendering Adapter was chosen.");
+                }
+
+                return ret;
+            }
+        }
+
+        Err(GraphicsError::AdapterNotFound)
+    }
 }
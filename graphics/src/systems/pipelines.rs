@@ -25,10 +25,22 @@ pub trait PipeLineLayout: Pod + Zeroable {
     }
 }
 
+/// Recreates a [`wgpu::RenderPipeline`] from its stored [`PipeLineLayout`].
+///
+type PipelineBuilder = Box<
+    dyn Fn(
+        &mut GpuDevice,
+        &mut LayoutStorage,
+        wgpu::TextureFormat,
+    ) -> wgpu::RenderPipeline,
+>;
+
 /// [`wgpu::RenderPipeline`] Storage using a hashmap.
 ///
 pub struct PipelineStorage {
     pub(crate) map: AHashMap<(TypeId, Vec<u8>), wgpu::RenderPipeline>,
+    /// Used to rebuild every Pipeline when the Render Attachments change.
+    builders: AHashMap<(TypeId, Vec<u8>), PipelineBuilder>,
 }
 
 impl PipelineStorage {
@@ -37,6 +49,7 @@ impl PipelineStorage {
     pub fn new() -> Self {
         Self {
             map: AHashMap::default(),
+            builders: AHashMap::default(),
         }
     }
 
@@ -52,9 +65,32 @@ impl PipelineStorage {
         let key = pipeline.layout_key();
 
         self.map.insert(
-            key,
+            key.clone(),
             pipeline.create_layout(device, layout_storage, surface_format),
         );
+        self.builders.insert(
+            key,
+            Box::new(move |device, layout_storage, surface_format| {
+                pipeline.create_layout(device, layout_storage, surface_format)
+            }),
+        );
+    }
+
+    /// Recreates every stored [`wgpu::RenderPipeline`]. Used when the
+    /// Multisample count or surface format changes.
+    ///
+    pub fn rebuild_pipelines(
+        &mut self,
+        device: &mut GpuDevice,
+        layout_storage: &mut LayoutStorage,
+        surface_format: wgpu::TextureFormat,
+    ) {
+        for (key, builder) in &self.builders {
+            self.map.insert(
+                key.clone(),
+                builder(device, layout_storage, surface_format),
+            );
+        }
     }
 
     /// Retrieves a Reference to a [`wgpu::RenderPipeline`] within the internal map for rendering.
@@ -1,7 +1,7 @@
 use crate::{
     BufferPass, BufferStore, GpuDevice, GpuWindow, GraphicsError, Index,
     Layout, LayoutStorage, OtherError, PipeLineLayout, PipelineStorage,
-    StaticVertexBuffer,
+    StaticVertexBuffer, SurfaceSettings,
 };
 use cosmic_text::FontSystem;
 use slotmap::SlotMap;
@@ -20,6 +20,11 @@ pub struct GpuRenderer {
     pub(crate) pipeline_storage: PipelineStorage,
     pub(crate) depthbuffer: wgpu::TextureView,
     pub(crate) framebuffer: Option<wgpu::TextureView>,
+    pub(crate) msaa_framebuffer: Option<wgpu::TextureView>,
+    /// Size the Depth and Multisampled Buffers were created with.
+    pub(crate) target_size: PhysicalSize<u32>,
+    /// Called after the size dependent Buffers got recreated.
+    pub(crate) resize_hook: Option<ResizeHook>,
     pub(crate) frame: Option<wgpu::SurfaceTexture>,
     pub font_sys: FontSystem,
     pub buffer_object: StaticVertexBuffer,
@@ -49,6 +54,10 @@ where
     }
 }
 
+/// Callback used to notify the App the Renderer was resized.
+///
+pub type ResizeHook = Box<dyn FnMut(PhysicalSize<u32>) + Send>;
+
 impl GpuRenderer {
     /// Creates a New GpuRenderer.
     ///
@@ -56,6 +65,10 @@ impl GpuRenderer {
         let buffer_object = StaticVertexBuffer::create_buffer(&device);
         let depth_buffer = window.create_depth_texture(&device);
         let backend = window.adapter.get_info().backend;
+        let target_size = PhysicalSize::new(
+            window.size.width as u32,
+            window.size.height as u32,
+        );
 
         Self {
             window,
@@ -65,6 +78,9 @@ impl GpuRenderer {
             pipeline_storage: PipelineStorage::new(),
             depthbuffer: depth_buffer,
             framebuffer: None,
+            msaa_framebuffer: None,
+            target_size,
+            resize_hook: None,
             frame: None,
             font_sys: FontSystem::new(),
             buffer_object,
@@ -78,13 +94,64 @@ impl GpuRenderer {
         self.window.adapter()
     }
 
-    /// Resizes the Window.
+    /// Returns the features the Device allows for a Texture format.
+    /// See [`GpuDevice::texture_format_features`].
+    ///
+    pub fn texture_format_features(
+        &self,
+        format: wgpu::TextureFormat,
+    ) -> wgpu::TextureFormatFeatures {
+        self.device.texture_format_features(self.adapter(), format)
+    }
+
+    /// Resizes the Window along with the Depth and Multisampled Buffers.
     ///
     pub fn resize(
         &mut self,
         size: PhysicalSize<u32>,
     ) -> Result<(), GraphicsError> {
-        self.window.resize(&self.device, size)
+        self.window.resize(&self.device, size)?;
+        self.update_size_targets();
+        Ok(())
+    }
+
+    /// Sets a Hook called with the new size each time the Renderer recreated
+    /// its size dependent Buffers. The Renderer is still borrowed while the
+    /// Hook runs, so record the size in it and resize the Apps own
+    /// [`crate::RenderTarget`]'s, Systems and Projections after
+    /// [`GpuRenderer::resize`] returns.
+    ///
+    pub fn set_resize_hook(
+        &mut self,
+        hook: impl FnMut(PhysicalSize<u32>) + Send + 'static,
+    ) {
+        self.resize_hook = Some(Box::new(hook));
+    }
+
+    /// Removes the Hook set with [`GpuRenderer::set_resize_hook`].
+    ///
+    pub fn clear_resize_hook(&mut self) {
+        self.resize_hook = None;
+    }
+
+    /// Recreates the Depth and Multisampled Buffers if the Window size no
+    /// longer matches them and notifies the resize Hook.
+    ///
+    fn update_size_targets(&mut self) {
+        let size = PhysicalSize::new(
+            self.window.size.width as u32,
+            self.window.size.height as u32,
+        );
+
+        if size == self.target_size || size.width == 0 || size.height == 0 {
+            return;
+        }
+
+        self.update_depth_texture();
+
+        if let Some(hook) = &mut self.resize_hook {
+            hook(size);
+        }
     }
 
     /// Returns a reference to the Optional [`wgpu::TextureView`]: frame buffer.
@@ -111,10 +178,22 @@ impl GpuRenderer {
         self.window.inner_size
     }
 
-    /// Returns a reference to [`wgpu::Surface`].
+    /// Returns a reference to [`wgpu::Surface`] if not Headless.
+    ///
+    pub fn surface(&self) -> Option<&wgpu::Surface<'static>> {
+        self.window.surface()
+    }
+
+    /// Returns true if the renderer was created Headless and renders offscreen.
+    ///
+    pub fn is_headless(&self) -> bool {
+        self.window.is_headless()
+    }
+
+    /// Returns a reference to the offscreen [`wgpu::Texture`] if Headless.
     ///
-    pub fn surface(&self) -> &wgpu::Surface {
-        &self.window.surface
+    pub fn offscreen_texture(&self) -> Option<&wgpu::Texture> {
+        self.window.offscreen_texture()
     }
 
     /// Returns the surfaces [`wgpu::TextureFormat`].
@@ -131,7 +210,11 @@ impl GpuRenderer {
         &mut self,
         event: &WindowEvent,
     ) -> Result<bool, GraphicsError> {
-        let frame = match self.window.update(&self.device, event)? {
+        let frame = self.window.update(&self.device, event)?;
+
+        self.update_size_targets();
+
+        let frame = match frame {
             Some(frame) => frame,
             _ => return Ok(false),
         };
@@ -146,22 +229,211 @@ impl GpuRenderer {
         Ok(true)
     }
 
-    /// Returns a reference to [`Window`].
+    /// Called to update the Optional Framebuffer with the offscreen Texture
+    /// when Headless. This replaces [`GpuRenderer::update`] as there are no
+    /// Window events to drive rendering.
+    /// Returns false if the renderer is not Headless.
+    ///
+    pub fn update_offscreen(&mut self) -> Result<bool, GraphicsError> {
+        let Some(texture) = self.window.offscreen_texture() else {
+            return Ok(false);
+        };
+
+        self.framebuffer =
+            Some(texture.create_view(&wgpu::TextureViewDescriptor::default()));
+
+        Ok(true)
+    }
+
+    /// Returns a reference to the current [`wgpu::SurfaceConfiguration`].
+    ///
+    pub fn surface_config(&self) -> &wgpu::SurfaceConfiguration {
+        self.window.surface_config()
+    }
+
+    /// Returns the current [`SurfaceSettings`].
+    ///
+    pub fn surface_settings(&self) -> SurfaceSettings {
+        self.window.surface_settings()
+    }
+
+    /// Returns the [`wgpu::SurfaceCapabilities`] if not Headless.
+    ///
+    pub fn surface_capabilities(&self) -> Option<wgpu::SurfaceCapabilities> {
+        self.window.surface_capabilities()
+    }
+
+    /// Changes the present mode, alpha mode and frame latency of the Surface
+    /// without recreating the renderer.
+    ///
+    /// Returns a [`GraphicsError`] and keeps the current settings if one of
+    /// them is not supported by the Surface.
+    ///
+    pub fn reconfigure_surface(
+        &mut self,
+        settings: SurfaceSettings,
+    ) -> Result<(), GraphicsError> {
+        self.window.reconfigure(&self.device, settings)
+    }
+
+    /// Sets the present mode of the Surface. Fifo is VSync on and
+    /// Immediate or Mailbox is VSync off.
     ///
-    pub fn window(&self) -> &Window {
-        &self.window.window
+    pub fn set_present_mode(
+        &mut self,
+        present_mode: wgpu::PresentMode,
+    ) -> Result<(), GraphicsError> {
+        self.reconfigure_surface(SurfaceSettings {
+            present_mode,
+            ..self.surface_settings()
+        })
     }
 
-    /// Updates the Internally Stored Depth Buffer.
+    /// Sets VSync on using Fifo or off using AutoNoVsync.
+    ///
+    pub fn set_vsync(&mut self, vsync: bool) -> Result<(), GraphicsError> {
+        self.set_present_mode(if vsync {
+            wgpu::PresentMode::Fifo
+        } else {
+            wgpu::PresentMode::AutoNoVsync
+        })
+    }
+
+    /// Sets the maximum amount of frames that may be queued up.
+    ///
+    pub fn set_frame_latency(
+        &mut self,
+        desired_maximum_frame_latency: u32,
+    ) -> Result<(), GraphicsError> {
+        self.reconfigure_surface(SurfaceSettings {
+            desired_maximum_frame_latency,
+            ..self.surface_settings()
+        })
+    }
+
+    /// Sets how the Window blends with what is behind it.
+    ///
+    pub fn set_alpha_mode(
+        &mut self,
+        alpha_mode: wgpu::CompositeAlphaMode,
+    ) -> Result<(), GraphicsError> {
+        self.reconfigure_surface(SurfaceSettings {
+            alpha_mode,
+            ..self.surface_settings()
+        })
+    }
+
+    /// Returns a reference to [`Window`] if not Headless.
+    ///
+    pub fn window(&self) -> Option<&Window> {
+        self.window.window()
+    }
+
+    /// Updates the Internally Stored Depth Buffer and Multisampled
+    /// Color Buffer. This is done automatically when the Window gets resized.
     ///
     pub fn update_depth_texture(&mut self) {
         self.depthbuffer = self.window.create_depth_texture(&self.device);
+        self.msaa_framebuffer = self.window.create_msaa_texture(&self.device);
+        self.target_size = PhysicalSize::new(
+            self.window.size.width as u32,
+            self.window.size.height as u32,
+        );
+    }
+
+    /// Returns the Multisample count the Renderer draws with.
+    ///
+    pub fn sample_count(&self) -> u32 {
+        self.device.sample_count()
+    }
+
+    /// Sets the Multisample count used to smooth edges of Meshes and Rects.
+    /// Recreates the Depth and Multisampled Color Buffers and rebuilds all
+    /// Pipelines. A count of 1 disables Multisampling.
+    /// [`crate::RenderTarget`]'s must be created or resized after this.
+    ///
+    /// Returns [`GraphicsError::UnsupportedSampleCount`] if the device does
+    /// not support the count for the surface or depth format.
+    ///
+    pub fn set_sample_count(
+        &mut self,
+        count: u32,
+    ) -> Result<(), GraphicsError> {
+        if count == self.sample_count() {
+            return Ok(());
+        }
+
+        let supported =
+            [self.surface_format(), wgpu::TextureFormat::Depth32Float]
+                .iter()
+                .all(|&format| {
+                    self.texture_format_features(format)
+                        .flags
+                        .sample_count_supported(count)
+                });
+
+        if !supported {
+            return Err(GraphicsError::UnsupportedSampleCount(count));
+        }
+
+        self.device.sample_count = count;
+        self.update_depth_texture();
+        self.pipeline_storage.rebuild_pipelines(
+            &mut self.device,
+            &mut self.layout_storage,
+            self.window.surface_format,
+        );
+
+        Ok(())
+    }
+
+    /// Returns a reference to the Multisampled Color Buffer if Multisampling
+    /// is enabled.
+    ///
+    pub fn msaa_buffer(&self) -> Option<&wgpu::TextureView> {
+        self.msaa_framebuffer.as_ref()
+    }
+
+    /// Returns a Color Attachment for the frame buffer to begin a
+    /// [`wgpu::RenderPass`] with. When Multisampling it renders into the
+    /// Multisampled Color Buffer and resolves into the frame buffer.
+    /// Returns None if the frame buffer does not Exist.
+    ///
+    pub fn color_attachment(
+        &self,
+        load: wgpu::LoadOp<wgpu::Color>,
+    ) -> Option<wgpu::RenderPassColorAttachment<'_>> {
+        let frame = self.framebuffer.as_ref()?;
+        let (view, resolve_target) = match &self.msaa_framebuffer {
+            Some(msaa) => (msaa, Some(frame)),
+            None => (frame, None),
+        };
+
+        Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target,
+            ops: wgpu::Operations {
+                load,
+                store: wgpu::StoreOp::Store,
+            },
+        })
     }
 
     /// Presents the Current frame Buffer to the Window if Some().
     /// If the frame buffer does not Exist will return a Error.
+    /// When Headless this only releases the frame buffer as the
+    /// offscreen Texture keeps its contents.
     ///
     pub fn present(&mut self) -> Result<(), GraphicsError> {
+        if self.is_headless() {
+            return match self.framebuffer.take() {
+                Some(_) => Ok(()),
+                None => Err(GraphicsError::Other(OtherError::new(
+                    "Frame does not Exist. Did you forget to update_offscreen the renderer?",
+                ))),
+            };
+        }
+
         self.framebuffer = None;
 
         match self.frame.take() {
@@ -267,42 +539,60 @@ impl GpuRenderer {
             &mut self.device,
             &mut self.layout_storage,
             surface_format,
-            crate::ImageRenderPipeline,
+            crate::ImageRenderPipeline::default(),
         );
 
         self.pipeline_storage.create_pipeline(
             &mut self.device,
             &mut self.layout_storage,
             surface_format,
-            crate::MapRenderPipeline,
+            crate::MapRenderPipeline::default(),
         );
 
         self.pipeline_storage.create_pipeline(
             &mut self.device,
             &mut self.layout_storage,
             surface_format,
-            crate::TextRenderPipeline,
+            crate::TextRenderPipeline::default(),
         );
 
         self.pipeline_storage.create_pipeline(
             &mut self.device,
             &mut self.layout_storage,
             surface_format,
-            crate::Mesh2DRenderPipeline,
+            crate::Mesh2DRenderPipeline::default(),
         );
 
         self.pipeline_storage.create_pipeline(
             &mut self.device,
             &mut self.layout_storage,
             surface_format,
-            crate::LightRenderPipeline,
+            crate::LightRenderPipeline::default(),
         );
 
         self.pipeline_storage.create_pipeline(
             &mut self.device,
             &mut self.layout_storage,
             surface_format,
-            crate::RectRenderPipeline,
+            crate::RectRenderPipeline::default(),
+        );
+    }
+
+    /// Creates a single [`wgpu::RenderPipeline`] from Generic K if it does
+    /// not already Exist.
+    ///
+    pub fn create_pipeline<K: PipeLineLayout>(&mut self, pipeline: K) {
+        if self.pipeline_storage.get_pipeline(pipeline).is_some() {
+            return;
+        }
+
+        let surface_format = self.surface_format();
+
+        self.pipeline_storage.create_pipeline(
+            &mut self.device,
+            &mut self.layout_storage,
+            surface_format,
+            pipeline,
         );
     }
 
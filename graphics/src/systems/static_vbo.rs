@@ -84,7 +84,7 @@ impl StaticVertexBuffer {
     }
 
     /// Returns the [`wgpu::BufferSlice`] of indices.
-    pub fn indices(&self) -> wgpu::BufferSlice {
+    pub fn indices(&self) -> wgpu::BufferSlice<'_> {
         self.ibo.slice(..)
     }
 
@@ -94,7 +94,7 @@ impl StaticVertexBuffer {
     }
 
     /// Returns the [`wgpu::BufferSlice`] of vertices.
-    pub fn vertices(&self) -> wgpu::BufferSlice {
+    pub fn vertices(&self) -> wgpu::BufferSlice<'_> {
         self.vbo.slice(..)
     }
 }
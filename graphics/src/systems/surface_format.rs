@@ -0,0 +1,87 @@
+use crate::GraphicsError;
+use wgpu::TextureFormat;
+
+/// Policy used to pick the [`wgpu::Surface`]'s [`TextureFormat`] out of
+/// the formats it supports.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SurfaceFormatPolicy {
+    /// Prefer 8 bit sRGB formats, falling back to 8 bit linear formats with
+    /// manual gamma.
+    #[default]
+    Srgb,
+    /// Prefer the HDR Rgba16Float format, falling back to [`SurfaceFormatPolicy::Srgb`].
+    PreferHdr,
+    /// Prefer 8 bit linear formats with manual gamma, falling back to 8 bit
+    /// sRGB formats.
+    Linear,
+}
+
+impl SurfaceFormatPolicy {
+    /// Returns the formats the policy accepts in order of preference.
+    ///
+    pub fn preferred_formats(&self) -> &'static [TextureFormat] {
+        match self {
+            SurfaceFormatPolicy::Srgb => &[
+                TextureFormat::Rgba8UnormSrgb,
+                TextureFormat::Bgra8UnormSrgb,
+                TextureFormat::Rgba8Unorm,
+                TextureFormat::Bgra8Unorm,
+            ],
+            SurfaceFormatPolicy::PreferHdr => &[
+                TextureFormat::Rgba16Float,
+                TextureFormat::Rgba8UnormSrgb,
+                TextureFormat::Bgra8UnormSrgb,
+                TextureFormat::Rgba8Unorm,
+                TextureFormat::Bgra8Unorm,
+            ],
+            SurfaceFormatPolicy::Linear => &[
+                TextureFormat::Rgba8Unorm,
+                TextureFormat::Bgra8Unorm,
+                TextureFormat::Rgba8UnormSrgb,
+                TextureFormat::Bgra8UnormSrgb,
+            ],
+        }
+    }
+
+    /// Picks the first preferred format the Surface supports.
+    ///
+    /// Returns [`GraphicsError::NoSurfaceFormat`] if none are supported.
+    ///
+    pub fn select(
+        &self,
+        supported: &[TextureFormat],
+    ) -> Result<TextureFormat, GraphicsError> {
+        self.preferred_formats()
+            .iter()
+            .copied()
+            .find(|format| supported.contains(format))
+            .ok_or_else(|| GraphicsError::NoSurfaceFormat(supported.to_vec()))
+    }
+}
+
+/// Returns true if rendering into the format needs the shaders to gamma
+/// encode colors by hand. This is the case for non sRGB 8 bit formats,
+/// Float formats are linear and get no gamma.
+///
+pub fn needs_manual_gamma(format: TextureFormat) -> bool {
+    matches!(
+        format,
+        TextureFormat::Rgba8Unorm
+            | TextureFormat::Bgra8Unorm
+            | TextureFormat::Rgb10a2Unorm
+    )
+}
+
+/// Returns the Pipeline override constants the renderers shaders need for
+/// the format.
+///
+pub(crate) fn gamma_constants(
+    format: TextureFormat,
+) -> &'static [(&'static str, f64)] {
+    if needs_manual_gamma(format) {
+        &[("manual_gamma", 1.0)]
+    } else {
+        &[]
+    }
+}
@@ -1,7 +1,16 @@
+mod compressed;
+mod loader;
+mod rendertarget;
+mod sampler;
 mod texture;
 mod texturegroup;
 mod texturelayout;
+mod watcher;
 
+pub use loader::{TextureHandle, TextureLoadState, TextureLoader};
+pub use rendertarget::RenderTarget;
+pub use sampler::SamplerSettings;
 pub use texture::Texture;
-pub use texturegroup::TextureGroup;
+pub use texturegroup::{AsTextureGroup, TextureGroup};
 pub use texturelayout::TextureLayout;
+pub use watcher::{TextureReload, TextureWatcher};
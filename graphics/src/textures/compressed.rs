@@ -0,0 +1,238 @@
+use crate::GraphicsError;
+use ddsfile::{D3DFormat, Dds, DxgiFormat};
+use wgpu::{AstcBlock, AstcChannel, TextureFormat};
+
+/// Identifier every KTX2 file starts with.
+const KTX2_MAGIC: [u8; 12] = [
+    0xAB, b'K', b'T', b'X', b' ', b'2', b'0', 0xBB, b'\r', b'\n', 0x1A, b'\n',
+];
+
+/// Identifier every DDS file starts with.
+const DDS_MAGIC: [u8; 4] = *b"DDS ";
+
+/// ASTC block sizes in the order KTX2 numbers its ASTC formats.
+const ASTC_BLOCKS: [AstcBlock; 14] = [
+    AstcBlock::B4x4,
+    AstcBlock::B5x4,
+    AstcBlock::B5x5,
+    AstcBlock::B6x5,
+    AstcBlock::B6x6,
+    AstcBlock::B8x5,
+    AstcBlock::B8x6,
+    AstcBlock::B8x8,
+    AstcBlock::B10x5,
+    AstcBlock::B10x6,
+    AstcBlock::B10x8,
+    AstcBlock::B10x10,
+    AstcBlock::B12x10,
+    AstcBlock::B12x12,
+];
+
+/// Pixels, Width and Height and format of a texture file's first image.
+///
+pub(crate) type CompressedImage = (Vec<u8>, (u32, u32), TextureFormat);
+
+/// If the data starts like a KTX2 file.
+///
+pub(crate) fn is_ktx2(data: &[u8]) -> bool {
+    data.starts_with(&KTX2_MAGIC)
+}
+
+/// If the data starts like a DDS file.
+///
+pub(crate) fn is_dds(data: &[u8]) -> bool {
+    data.starts_with(&DDS_MAGIC)
+}
+
+/// Returns the bytes an image of Width and Height takes in format.
+/// This counts whole blocks for block compressed formats.
+///
+pub(crate) fn image_byte_size(
+    format: TextureFormat,
+    width: u32,
+    height: u32,
+) -> Option<usize> {
+    let (block_width, block_height) = format.block_dimensions();
+    let block_size = format.block_copy_size(None)?;
+
+    Some(
+        width.div_ceil(block_width) as usize
+            * height.div_ceil(block_height) as usize
+            * block_size as usize,
+    )
+}
+
+/// Reads the first mip level of the first layer out of a KTX2 file.
+/// Supercompressed files are not supported.
+///
+pub(crate) fn decode_ktx2(
+    name: &str,
+    data: &[u8],
+) -> Result<CompressedImage, GraphicsError> {
+    let reader = ktx2::Reader::new(data)?;
+    let header = reader.header();
+    let unsupported = |reason: String| {
+        GraphicsError::UnsupportedTextureFile(name.to_owned(), reason)
+    };
+
+    if let Some(scheme) = header.supercompression_scheme {
+        return Err(unsupported(format!("supercompression {scheme:?}")));
+    }
+
+    let format = header
+        .format
+        .and_then(ktx2_format)
+        .ok_or_else(|| unsupported(format!("format {:?}", header.format)))?;
+    let size = (header.pixel_width, header.pixel_height.max(1));
+    let level = reader
+        .levels()
+        .next()
+        .ok_or_else(|| unsupported("no mip levels".to_owned()))?;
+
+    Ok((level.data.to_vec(), size, format))
+}
+
+/// Reads the first mip level of the first layer out of a DDS file.
+///
+pub(crate) fn decode_dds(
+    name: &str,
+    data: &[u8],
+) -> Result<CompressedImage, GraphicsError> {
+    let dds = Dds::read(data)?;
+    let format = dds
+        .get_dxgi_format()
+        .and_then(dxgi_format)
+        .or_else(|| dds.get_d3d_format().and_then(d3d_format))
+        .ok_or_else(|| {
+            GraphicsError::UnsupportedTextureFile(
+                name.to_owned(),
+                format!(
+                    "format {:?} {:?}",
+                    dds.get_dxgi_format(),
+                    dds.get_d3d_format()
+                ),
+            )
+        })?;
+
+    Ok((
+        dds.get_data(0)?.to_vec(),
+        (dds.get_width(), dds.get_height()),
+        format,
+    ))
+}
+
+/// Maps a KTX2 format onto the matching [`TextureFormat`].
+///
+fn ktx2_format(format: ktx2::Format) -> Option<TextureFormat> {
+    use ktx2::Format as K;
+
+    Some(match format {
+        K::R8_UNORM => TextureFormat::R8Unorm,
+        K::R8G8_UNORM => TextureFormat::Rg8Unorm,
+        K::R8G8B8A8_UNORM => TextureFormat::Rgba8Unorm,
+        K::R8G8B8A8_SRGB => TextureFormat::Rgba8UnormSrgb,
+        K::B8G8R8A8_UNORM => TextureFormat::Bgra8Unorm,
+        K::B8G8R8A8_SRGB => TextureFormat::Bgra8UnormSrgb,
+        K::BC1_RGB_UNORM_BLOCK | K::BC1_RGBA_UNORM_BLOCK => {
+            TextureFormat::Bc1RgbaUnorm
+        }
+        K::BC1_RGB_SRGB_BLOCK | K::BC1_RGBA_SRGB_BLOCK => {
+            TextureFormat::Bc1RgbaUnormSrgb
+        }
+        K::BC2_UNORM_BLOCK => TextureFormat::Bc2RgbaUnorm,
+        K::BC2_SRGB_BLOCK => TextureFormat::Bc2RgbaUnormSrgb,
+        K::BC3_UNORM_BLOCK => TextureFormat::Bc3RgbaUnorm,
+        K::BC3_SRGB_BLOCK => TextureFormat::Bc3RgbaUnormSrgb,
+        K::BC4_UNORM_BLOCK => TextureFormat::Bc4RUnorm,
+        K::BC4_SNORM_BLOCK => TextureFormat::Bc4RSnorm,
+        K::BC5_UNORM_BLOCK => TextureFormat::Bc5RgUnorm,
+        K::BC5_SNORM_BLOCK => TextureFormat::Bc5RgSnorm,
+        K::BC6H_UFLOAT_BLOCK => TextureFormat::Bc6hRgbUfloat,
+        K::BC6H_SFLOAT_BLOCK => TextureFormat::Bc6hRgbFloat,
+        K::BC7_UNORM_BLOCK => TextureFormat::Bc7RgbaUnorm,
+        K::BC7_SRGB_BLOCK => TextureFormat::Bc7RgbaUnormSrgb,
+        K::ETC2_R8G8B8_UNORM_BLOCK => TextureFormat::Etc2Rgb8Unorm,
+        K::ETC2_R8G8B8_SRGB_BLOCK => TextureFormat::Etc2Rgb8UnormSrgb,
+        K::ETC2_R8G8B8A1_UNORM_BLOCK => TextureFormat::Etc2Rgb8A1Unorm,
+        K::ETC2_R8G8B8A1_SRGB_BLOCK => TextureFormat::Etc2Rgb8A1UnormSrgb,
+        K::ETC2_R8G8B8A8_UNORM_BLOCK => TextureFormat::Etc2Rgba8Unorm,
+        K::ETC2_R8G8B8A8_SRGB_BLOCK => TextureFormat::Etc2Rgba8UnormSrgb,
+        K::EAC_R11_UNORM_BLOCK => TextureFormat::EacR11Unorm,
+        K::EAC_R11_SNORM_BLOCK => TextureFormat::EacR11Snorm,
+        K::EAC_R11G11_UNORM_BLOCK => TextureFormat::EacRg11Unorm,
+        K::EAC_R11G11_SNORM_BLOCK => TextureFormat::EacRg11Snorm,
+        _ => {
+            let value = format.value();
+            let unorm = K::ASTC_4x4_UNORM_BLOCK.value();
+            let hdr = K::ASTC_4x4_SFLOAT_BLOCK.value();
+
+            let (block, channel) = if let Some(index) = value
+                .checked_sub(unorm)
+                .filter(|index| *index < ASTC_BLOCKS.len() as u32 * 2)
+            {
+                (
+                    index / 2,
+                    if index % 2 == 0 {
+                        AstcChannel::Unorm
+                    } else {
+                        AstcChannel::UnormSrgb
+                    },
+                )
+            } else {
+                let index = value
+                    .checked_sub(hdr)
+                    .filter(|index| *index < ASTC_BLOCKS.len() as u32)?;
+
+                (index, AstcChannel::Hdr)
+            };
+
+            TextureFormat::Astc {
+                block: ASTC_BLOCKS[block as usize],
+                channel,
+            }
+        }
+    })
+}
+
+/// Maps a DXGI format onto the matching [`TextureFormat`].
+///
+fn dxgi_format(format: DxgiFormat) -> Option<TextureFormat> {
+    Some(match format {
+        DxgiFormat::R8_UNorm => TextureFormat::R8Unorm,
+        DxgiFormat::R8G8_UNorm => TextureFormat::Rg8Unorm,
+        DxgiFormat::R8G8B8A8_UNorm => TextureFormat::Rgba8Unorm,
+        DxgiFormat::R8G8B8A8_UNorm_sRGB => TextureFormat::Rgba8UnormSrgb,
+        DxgiFormat::B8G8R8A8_UNorm => TextureFormat::Bgra8Unorm,
+        DxgiFormat::B8G8R8A8_UNorm_sRGB => TextureFormat::Bgra8UnormSrgb,
+        DxgiFormat::BC1_UNorm => TextureFormat::Bc1RgbaUnorm,
+        DxgiFormat::BC1_UNorm_sRGB => TextureFormat::Bc1RgbaUnormSrgb,
+        DxgiFormat::BC2_UNorm => TextureFormat::Bc2RgbaUnorm,
+        DxgiFormat::BC2_UNorm_sRGB => TextureFormat::Bc2RgbaUnormSrgb,
+        DxgiFormat::BC3_UNorm => TextureFormat::Bc3RgbaUnorm,
+        DxgiFormat::BC3_UNorm_sRGB => TextureFormat::Bc3RgbaUnormSrgb,
+        DxgiFormat::BC4_UNorm => TextureFormat::Bc4RUnorm,
+        DxgiFormat::BC4_SNorm => TextureFormat::Bc4RSnorm,
+        DxgiFormat::BC5_UNorm => TextureFormat::Bc5RgUnorm,
+        DxgiFormat::BC5_SNorm => TextureFormat::Bc5RgSnorm,
+        DxgiFormat::BC6H_UF16 => TextureFormat::Bc6hRgbUfloat,
+        DxgiFormat::BC6H_SF16 => TextureFormat::Bc6hRgbFloat,
+        DxgiFormat::BC7_UNorm => TextureFormat::Bc7RgbaUnorm,
+        DxgiFormat::BC7_UNorm_sRGB => TextureFormat::Bc7RgbaUnormSrgb,
+        _ => return None,
+    })
+}
+
+/// Maps a legacy D3D format onto the matching [`TextureFormat`].
+/// These carry no color space so they are treated as linear.
+///
+fn d3d_format(format: D3DFormat) -> Option<TextureFormat> {
+    Some(match format {
+        D3DFormat::L8 => TextureFormat::R8Unorm,
+        D3DFormat::A8B8G8R8 => TextureFormat::Rgba8Unorm,
+        D3DFormat::A8R8G8B8 => TextureFormat::Bgra8Unorm,
+        D3DFormat::DXT1 => TextureFormat::Bc1RgbaUnorm,
+        D3DFormat::DXT3 => TextureFormat::Bc2RgbaUnorm,
+        D3DFormat::DXT5 => TextureFormat::Bc3RgbaUnorm,
+        _ => return None,
+    })
+}
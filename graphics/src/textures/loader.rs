@@ -0,0 +1,364 @@
+use crate::{AHashMap, AtlasSet, GpuRenderer, GraphicsError, Texture};
+use log::{error, warn};
+#[cfg(not(feature = "rayon"))]
+use std::sync::{Arc, Mutex};
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::mpsc::{Receiver, Sender, channel},
+};
+
+/// Most worker threads a [`TextureLoader`] starts without rayon.
+///
+#[cfg(not(feature = "rayon"))]
+const MAX_WORKERS: usize = 4;
+
+/// Handle to a Texture requested from a [`TextureLoader`].
+/// Resolves to the [`AtlasSet`] Index once uploaded.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TextureHandle(usize);
+
+/// Progress of a Texture requested from a [`TextureLoader`].
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TextureLoadState {
+    /// Still being decoded or waiting to be uploaded.
+    Loading,
+    /// Uploaded into the [`AtlasSet`] at the Index.
+    Ready(usize),
+    /// Decoding or uploading failed. The reason gets logged and the
+    /// Texture can be requested again.
+    Failed,
+}
+
+/// Source a [`TextureLoader`] decodes a Texture from.
+///
+enum TextureSource {
+    File(PathBuf),
+    Memory(Vec<u8>),
+}
+
+/// Decoded result sent back from a worker thread.
+///
+type Decoded = (TextureHandle, Result<Texture, GraphicsError>);
+
+/// Decode job run by a worker thread.
+///
+#[cfg(not(feature = "rayon"))]
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Decodes Textures on worker threads and uploads them into an
+/// [`AtlasSet`] a limited amount per frame to avoid hitches.
+///
+/// Uses rayon's thread pool when the rayon feature is enabled, otherwise
+/// up to 4 worker threads, limited by the available parallelism, that
+/// are started as requests come in and stop when the loader is dropped.
+///
+/// # Suggested usage
+/// Request Textures with [`TextureLoader::load`] and call
+/// [`TextureLoader::update`] once per frame. Draw with
+/// [`TextureLoader::index`] which returns the placeholder until the
+/// Texture is ready, then rebuild the objects using it once it resolves.
+///
+pub struct TextureLoader {
+    /// State of each requested Texture.
+    states: Vec<TextureLoadState>,
+    /// Key each Texture gets stored under in the [`AtlasSet`].
+    names: Vec<String>,
+    /// Key to Handle lookups so Textures are only requested once unless
+    /// they failed.
+    lookup: AHashMap<String, TextureHandle>,
+    /// Decoded Textures waiting to be uploaded.
+    decoded: VecDeque<(TextureHandle, Texture)>,
+    sender: Sender<Decoded>,
+    receiver: Receiver<Decoded>,
+    /// Requests still being decoded.
+    decoding: usize,
+    /// Max amount of Textures uploaded per [`TextureLoader::update`].
+    uploads_per_frame: usize,
+    /// [`AtlasSet`] Index returned for Textures that are not ready yet.
+    placeholder: Option<usize>,
+    /// Queue the worker threads take their jobs from.
+    #[cfg(not(feature = "rayon"))]
+    jobs: Sender<Job>,
+    #[cfg(not(feature = "rayon"))]
+    job_receiver: Arc<Mutex<Receiver<Job>>>,
+    /// Worker threads started so far.
+    #[cfg(not(feature = "rayon"))]
+    workers: usize,
+}
+
+impl TextureLoader {
+    /// Creates a new [`TextureLoader`].
+    ///
+    /// # Arguments
+    /// - uploads_per_frame: Max amount of Textures uploaded each time
+    ///   [`TextureLoader::update`] is called. Minimum of 1.
+    ///
+    pub fn new(uploads_per_frame: usize) -> Self {
+        let (sender, receiver) = channel();
+        #[cfg(not(feature = "rayon"))]
+        let (jobs, job_receiver) = channel();
+
+        Self {
+            states: Vec::new(),
+            names: Vec::new(),
+            lookup: AHashMap::default(),
+            decoded: VecDeque::new(),
+            sender,
+            receiver,
+            decoding: 0,
+            uploads_per_frame: uploads_per_frame.max(1),
+            placeholder: None,
+            #[cfg(not(feature = "rayon"))]
+            jobs,
+            #[cfg(not(feature = "rayon"))]
+            job_receiver: Arc::new(Mutex::new(job_receiver)),
+            #[cfg(not(feature = "rayon"))]
+            workers: 0,
+        }
+    }
+
+    /// Sets the [`AtlasSet`] Index returned by [`TextureLoader::index`]
+    /// until a Texture is ready.
+    ///
+    pub fn set_placeholder(&mut self, placeholder: Option<usize>) -> &mut Self {
+        self.placeholder = placeholder;
+        self
+    }
+
+    /// Sets the Max amount of Textures uploaded per update.
+    ///
+    pub fn set_uploads_per_frame(&mut self, uploads_per_frame: usize) {
+        self.uploads_per_frame = uploads_per_frame.max(1);
+    }
+
+    /// Requests a Texture to be decoded from a File.
+    /// The path is used as key within the [`AtlasSet`] like
+    /// [`Texture::upload_from`].
+    ///
+    pub fn load(&mut self, path: impl AsRef<Path>) -> TextureHandle {
+        let path = path.as_ref();
+        let name = path.to_string_lossy().into_owned();
+
+        self.request(name, TextureSource::File(path.to_path_buf()))
+    }
+
+    /// Requests a Texture to be decoded from encoded image bytes.
+    ///
+    pub fn load_from_memory(
+        &mut self,
+        name: impl Into<String>,
+        data: Vec<u8>,
+    ) -> TextureHandle {
+        self.request(name.into(), TextureSource::Memory(data))
+    }
+
+    fn request(
+        &mut self,
+        name: String,
+        source: TextureSource,
+    ) -> TextureHandle {
+        if let Some(&handle) = self.lookup.get(&name) {
+            return handle;
+        }
+
+        let handle = TextureHandle(self.states.len());
+        let sender = self.sender.clone();
+        let texture_name = name.clone();
+        let job = move || {
+            let texture = match source {
+                TextureSource::File(path) => Texture::from_file(path),
+                TextureSource::Memory(data) => {
+                    Texture::from_memory(texture_name, &data)
+                }
+            };
+
+            // The loader was dropped so nobody is waiting for it.
+            let _ = sender.send((handle, texture));
+        };
+
+        #[cfg(feature = "rayon")]
+        rayon::spawn(job);
+
+        #[cfg(not(feature = "rayon"))]
+        self.spawn(Box::new(job));
+
+        self.states.push(TextureLoadState::Loading);
+        self.names.push(name.clone());
+        self.lookup.insert(name, handle);
+        self.decoding += 1;
+        handle
+    }
+
+    /// Queues a job for the worker threads, starting another one while
+    /// every worker is busy and the limit is not reached.
+    ///
+    #[cfg(not(feature = "rayon"))]
+    fn spawn(&mut self, job: Job) {
+        let limit = std::thread::available_parallelism()
+            .map_or(1, |count| count.get())
+            .min(MAX_WORKERS);
+
+        if self.workers < limit && self.decoding >= self.workers {
+            let jobs = Arc::clone(&self.job_receiver);
+
+            // Workers stop once the loader and with it the queue is dropped.
+            let spawned = std::thread::Builder::new()
+                .name("texture loader".into())
+                .spawn(move || {
+                    loop {
+                        let job = match jobs.lock() {
+                            Ok(jobs) => jobs.recv(),
+                            Err(_) => return,
+                        };
+
+                        match job {
+                            Ok(job) => job(),
+                            Err(_) => return,
+                        }
+                    }
+                });
+
+            match spawned {
+                Ok(_) => self.workers += 1,
+                Err(e) => error!("Texture loader worker failed to start: {e}"),
+            }
+        }
+
+        // The queue only closes once the loader is dropped.
+        let _ = self.jobs.send(job);
+    }
+
+    /// Marks a Texture as failed and forgets its key so it can be
+    /// requested again.
+    ///
+    fn fail(&mut self, handle: TextureHandle) {
+        self.states[handle.0] = TextureLoadState::Failed;
+
+        if self.lookup.get(&self.names[handle.0]) == Some(&handle) {
+            self.lookup.remove(&self.names[handle.0]);
+        }
+    }
+
+    /// Gathers decoded Textures and uploads up to uploads_per_frame of them
+    /// into the [`AtlasSet`].
+    ///
+    /// Returns the Handles that became ready during this call.
+    ///
+    pub fn update(
+        &mut self,
+        atlas: &mut AtlasSet<String, i32>,
+        renderer: &GpuRenderer,
+    ) -> Vec<TextureHandle> {
+        while let Ok((handle, texture)) = self.receiver.try_recv() {
+            self.decoding = self.decoding.saturating_sub(1);
+
+            match texture {
+                Ok(texture) => self.decoded.push_back((handle, texture)),
+                Err(e) => {
+                    error!(
+                        "Texture {} failed to decode: {e}",
+                        self.names[handle.0]
+                    );
+                    self.fail(handle);
+                }
+            }
+        }
+
+        let mut ready = Vec::new();
+
+        while ready.len() < self.uploads_per_frame {
+            let Some((handle, texture)) = self.decoded.pop_front() else {
+                break;
+            };
+            let (width, height) = texture.size();
+            let name = self.names[handle.0].clone();
+
+            if !texture.fits_format(atlas.format) {
+                error!(
+                    "Texture {name} is {:?} but the AtlasSet is {:?}.",
+                    texture.format(),
+                    atlas.format
+                );
+                self.fail(handle);
+                continue;
+            }
+
+            match atlas.try_upload(
+                name,
+                texture.bytes(),
+                width,
+                height,
+                0,
+                renderer,
+            ) {
+                Ok(Some(id)) => {
+                    ready.push(handle);
+                    self.states[handle.0] = TextureLoadState::Ready(id);
+                }
+                Ok(None) => {
+                    warn!(
+                        "Texture {} could not be placed in the AtlasSet.",
+                        self.names[handle.0]
+                    );
+                    self.fail(handle);
+                }
+                Err(e) => {
+                    error!(
+                        "Texture {} failed to upload: {e}",
+                        self.names[handle.0]
+                    );
+                    self.fail(handle);
+                }
+            }
+        }
+
+        ready
+    }
+
+    /// Returns the [`TextureLoadState`] of the Handle.
+    ///
+    pub fn state(&self, handle: TextureHandle) -> TextureLoadState {
+        self.states
+            .get(handle.0)
+            .copied()
+            .unwrap_or(TextureLoadState::Failed)
+    }
+
+    /// Returns the [`AtlasSet`] Index if the Texture is ready.
+    ///
+    pub fn get(&self, handle: TextureHandle) -> Option<usize> {
+        match self.state(handle) {
+            TextureLoadState::Ready(id) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`AtlasSet`] Index if the Texture is ready, otherwise
+    /// the placeholder.
+    ///
+    pub fn index(&self, handle: TextureHandle) -> Option<usize> {
+        self.get(handle).or(self.placeholder)
+    }
+
+    /// Returns the key the Texture is stored under in the [`AtlasSet`].
+    ///
+    pub fn name(&self, handle: TextureHandle) -> Option<&str> {
+        self.names.get(handle.0).map(String::as_str)
+    }
+
+    /// Returns the amount of Textures still being decoded or waiting to be
+    /// uploaded.
+    ///
+    pub fn pending(&self) -> usize {
+        self.decoding + self.decoded.len()
+    }
+
+    /// If every requested Texture was uploaded or failed.
+    ///
+    pub fn is_idle(&self) -> bool {
+        self.pending() == 0
+    }
+}
@@ -0,0 +1,322 @@
+use crate::{
+    AsTextureGroup, GpuRenderer, GraphicsError, TextureGroup, TextureLayout,
+    needs_manual_gamma,
+};
+use wgpu::TextureUsages;
+
+/// Offscreen Texture with its own Depth Buffer that can be Rendered into and
+/// then drawn using an [`crate::Image`] or [`crate::Rect`].
+///
+/// Uses the [`GpuRenderer::surface_format`] so all the renderers pipelines
+/// can render into it. Use a [`crate::System`] with a Projection that
+/// matches the targets size when rendering into it.
+///
+/// Uses the [`GpuRenderer::sample_count`] it was created or last resized
+/// with, resolving into its Texture when Multisampling.
+///
+pub struct RenderTarget {
+    /// Texture in GRAM we render into.
+    pub texture: wgpu::Texture,
+    /// View used as the Color Attachment or Resolve Target.
+    pub view: wgpu::TextureView,
+    /// Multisampled Color View rendered into when Multisampling.
+    pub msaa: Option<wgpu::TextureView>,
+    /// Depth Buffer sized to the Texture.
+    pub depth: wgpu::TextureView,
+    /// Texture Bind group to draw the Target with.
+    pub texture_group: TextureGroup,
+    /// Format the Texture uses.
+    pub format: wgpu::TextureFormat,
+    /// Format [`crate::Image`]'s and [`crate::Rect`]'s sample the Texture
+    /// through.
+    sample_format: wgpu::TextureFormat,
+    /// Width and Height of the Texture.
+    size: (u32, u32),
+    /// Multisample count of the Attachments.
+    sample_count: u32,
+}
+
+impl RenderTarget {
+    /// Creates a new [`RenderTarget`] of width and height.
+    ///
+    /// Returns [`GraphicsError::UnsupportedFormat`] if the shaders gamma
+    /// encode by hand for the surface format and the Target can not be
+    /// sampled through a sRGB view, as drawing it would encode its colors
+    /// twice. This is the case on Gl without view formats.
+    ///
+    pub fn new(
+        renderer: &mut GpuRenderer,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, GraphicsError> {
+        let format = renderer.surface_format();
+
+        if !needs_manual_gamma(format) {
+            return Ok(Self::create(renderer, width, height, format));
+        }
+
+        let sample_format = format.add_srgb_suffix();
+
+        if sample_format == format
+            || !renderer
+                .adapter()
+                .get_downlevel_capabilities()
+                .flags
+                .contains(wgpu::DownlevelFlags::VIEW_FORMATS)
+        {
+            return Err(GraphicsError::UnsupportedFormat(format));
+        }
+
+        Ok(Self::create(renderer, width, height, sample_format))
+    }
+
+    /// Creates a new [`RenderTarget`] sampled through the surface format.
+    /// Only for Targets read by passes that handle the gamma themselves.
+    ///
+    pub(crate) fn new_linear(
+        renderer: &mut GpuRenderer,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let format = renderer.surface_format();
+
+        Self::create(renderer, width, height, format)
+    }
+
+    fn create(
+        renderer: &mut GpuRenderer,
+        width: u32,
+        height: u32,
+        sample_format: wgpu::TextureFormat,
+    ) -> Self {
+        let format = renderer.surface_format();
+        let layout = renderer.create_layout(TextureLayout);
+        let (texture, view, msaa, depth, texture_view) = Self::create_textures(
+            renderer,
+            format,
+            sample_format,
+            width,
+            height,
+        );
+
+        Self {
+            texture,
+            view,
+            msaa,
+            depth,
+            texture_group: TextureGroup::from_view(
+                renderer,
+                texture_view,
+                &layout,
+            ),
+            format,
+            sample_format,
+            size: (width.max(1), height.max(1)),
+            sample_count: renderer.sample_count(),
+        }
+    }
+
+    fn create_textures(
+        renderer: &GpuRenderer,
+        format: wgpu::TextureFormat,
+        sample_format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> (
+        wgpu::Texture,
+        wgpu::TextureView,
+        Option<wgpu::TextureView>,
+        wgpu::TextureView,
+        wgpu::TextureView,
+    ) {
+        let sample_count = renderer.sample_count();
+        let size = wgpu::Extent3d {
+            width: width.max(1),
+            height: height.max(1),
+            depth_or_array_layers: 1,
+        };
+
+        // Opengl can not tell a single layer Array apart from a 2D Texture.
+        let layers = if renderer.backend == wgpu::Backend::Gl {
+            2
+        } else {
+            1
+        };
+
+        let texture =
+            renderer.device().create_texture(&wgpu::TextureDescriptor {
+                label: Some("Render Target"),
+                size: wgpu::Extent3d {
+                    depth_or_array_layers: layers,
+                    ..size
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: TextureUsages::RENDER_ATTACHMENT
+                    | TextureUsages::TEXTURE_BINDING
+                    | TextureUsages::COPY_SRC
+                    | TextureUsages::COPY_DST,
+                view_formats: &[format, sample_format],
+            });
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor {
+            label: Some("Render Target View"),
+            dimension: Some(wgpu::TextureViewDimension::D2),
+            base_array_layer: 0,
+            array_layer_count: Some(1),
+            ..Default::default()
+        });
+
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor {
+            label: Some("Render Target Texture"),
+            format: Some(sample_format),
+            dimension: Some(wgpu::TextureViewDimension::D2Array),
+            aspect: wgpu::TextureAspect::All,
+            base_mip_level: 0,
+            mip_level_count: Some(1),
+            base_array_layer: 0,
+            array_layer_count: Some(layers),
+            usage: Some(TextureUsages::TEXTURE_BINDING),
+        });
+
+        let depth = renderer
+            .device()
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some("Render Target depth texture"),
+                size,
+                mip_level_count: 1,
+                sample_count,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Depth32Float,
+                usage: TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[wgpu::TextureFormat::Depth32Float],
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let msaa = (sample_count > 1).then(|| {
+            renderer
+                .device()
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some("Render Target msaa texture"),
+                    size,
+                    mip_level_count: 1,
+                    sample_count,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: &[format],
+                })
+                .create_view(&wgpu::TextureViewDescriptor::default())
+        });
+
+        (texture, view, msaa, depth, texture_view)
+    }
+
+    /// Recreates the Textures at a new width and height or when the
+    /// renderers Multisample count changed.
+    /// Anything using the Target must be updated afterwards.
+    ///
+    pub fn resize(&mut self, renderer: &GpuRenderer, width: u32, height: u32) {
+        if self.size == (width.max(1), height.max(1))
+            && self.sample_count == renderer.sample_count()
+        {
+            return;
+        }
+
+        let layout = renderer
+            .get_layout(TextureLayout)
+            .expect("TextureLayout was never created.");
+        let (texture, view, msaa, depth, texture_view) = Self::create_textures(
+            renderer,
+            self.format,
+            self.sample_format,
+            width,
+            height,
+        );
+
+        self.texture = texture;
+        self.view = view;
+        self.msaa = msaa;
+        self.depth = depth;
+        self.texture_group =
+            TextureGroup::from_view(renderer, texture_view, &layout);
+        self.size = (width.max(1), height.max(1));
+        self.sample_count = renderer.sample_count();
+    }
+
+    /// Returns the Width and Height of the Target.
+    ///
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Returns the Multisample count of the Targets Attachments.
+    ///
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+
+    /// Returns a reference to the Color [`wgpu::TextureView`].
+    ///
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    /// Returns a reference to the Depth [`wgpu::TextureView`].
+    ///
+    pub fn depth_view(&self) -> &wgpu::TextureView {
+        &self.depth
+    }
+
+    /// Returns a [`wgpu::BindGroup`] Reference to the Targets Texture Binding.
+    ///
+    pub fn bind_group(&self) -> &wgpu::BindGroup {
+        &self.texture_group.bind_group
+    }
+
+    /// Returns a Color Attachment to begin a [`wgpu::RenderPass`] with.
+    /// Renders into the Multisampled View and resolves into the Texture
+    /// when Multisampling.
+    ///
+    pub fn color_attachment(
+        &self,
+        load: wgpu::LoadOp<wgpu::Color>,
+    ) -> wgpu::RenderPassColorAttachment<'_> {
+        let (view, resolve_target) = match &self.msaa {
+            Some(msaa) => (msaa, Some(&self.view)),
+            None => (&self.view, None),
+        };
+
+        wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target,
+            ops: wgpu::Operations {
+                load,
+                store: wgpu::StoreOp::Store,
+            },
+        }
+    }
+
+    /// Returns a Depth Attachment to begin a [`wgpu::RenderPass`] with.
+    ///
+    pub fn depth_attachment(
+        &self,
+    ) -> wgpu::RenderPassDepthStencilAttachment<'_> {
+        wgpu::RenderPassDepthStencilAttachment {
+            view: &self.depth,
+            depth_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Clear(1.0),
+                store: wgpu::StoreOp::Store,
+            }),
+            stencil_ops: None,
+        }
+    }
+}
+
+impl AsTextureGroup for RenderTarget {
+    fn texture_group(&self) -> &TextureGroup {
+        &self.texture_group
+    }
+}
@@ -0,0 +1,97 @@
+/// Filtering and Addressing used when sampling a Texture.
+/// The Default is [`SamplerSettings::pixel_art`].
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SamplerSettings {
+    /// Filter used when the Texture is drawn larger than its size.
+    pub mag_filter: wgpu::FilterMode,
+    /// Filter used when the Texture is drawn smaller than its size.
+    pub min_filter: wgpu::FilterMode,
+    /// Filter used between mip levels.
+    pub mipmap_filter: wgpu::FilterMode,
+    /// How coordinates outside 0.0 to 1.0 are handled horizontally.
+    pub address_mode_u: wgpu::AddressMode,
+    /// How coordinates outside 0.0 to 1.0 are handled vertically.
+    pub address_mode_v: wgpu::AddressMode,
+    /// Max Anisotropy. Only used when every filter is Linear.
+    pub anisotropy_clamp: u16,
+    /// Highest mip level that can be sampled. 0 disables mipmapping.
+    /// [`crate::AtlasSet`] sets this from its own mip level count.
+    pub max_mip_level: u32,
+}
+
+impl SamplerSettings {
+    /// Nearest neighbour filtering clamped to the edge. Keeps pixel art sharp.
+    ///
+    pub fn pixel_art() -> Self {
+        Self {
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            anisotropy_clamp: 1,
+            max_mip_level: 0,
+        }
+    }
+
+    /// Linear filtering clamped to the edge. Used for smooth HD UI and Text.
+    ///
+    pub fn linear() -> Self {
+        Self {
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            ..Self::pixel_art()
+        }
+    }
+
+    /// Sets both the horizontal and vertical [`wgpu::AddressMode`].
+    ///
+    pub fn with_address_mode(
+        mut self,
+        address_mode: wgpu::AddressMode,
+    ) -> Self {
+        self.address_mode_u = address_mode;
+        self.address_mode_v = address_mode;
+        self
+    }
+
+    /// Sets the Max Anisotropy.
+    ///
+    pub fn with_anisotropy(mut self, anisotropy_clamp: u16) -> Self {
+        self.anisotropy_clamp = anisotropy_clamp;
+        self
+    }
+
+    /// Creates a [`wgpu::Sampler`] from the settings.
+    ///
+    pub fn create_sampler(&self, device: &wgpu::Device) -> wgpu::Sampler {
+        let linear = self.mag_filter == wgpu::FilterMode::Linear
+            && self.min_filter == wgpu::FilterMode::Linear
+            && self.mipmap_filter == wgpu::FilterMode::Linear;
+
+        device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Texture_sampler"),
+            address_mode_u: self.address_mode_u,
+            address_mode_v: self.address_mode_v,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: self.mag_filter,
+            min_filter: self.min_filter,
+            mipmap_filter: self.mipmap_filter,
+            lod_max_clamp: self.max_mip_level as f32,
+            anisotropy_clamp: if linear {
+                self.anisotropy_clamp.max(1)
+            } else {
+                1
+            },
+            ..Default::default()
+        })
+    }
+}
+
+impl Default for SamplerSettings {
+    fn default() -> Self {
+        Self::pixel_art()
+    }
+}
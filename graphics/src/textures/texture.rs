@@ -1,9 +1,12 @@
+use super::compressed::{
+    decode_dds, decode_ktx2, image_byte_size, is_dds, is_ktx2,
+};
 use crate::{Allocation, AtlasSet, GpuRenderer, GraphicsError, TileSheet};
 use image::{DynamicImage, GenericImageView, ImageFormat};
-use std::{io::Error, path::Path};
+use std::{fs, io::Error, path::Path};
 
 /// Holds the Textures information for Uploading to the GPU.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct Texture {
     /// full path.
     name: String,
@@ -11,6 +14,19 @@ pub struct Texture {
     pub bytes: Vec<u8>,
     /// Width and Height of the Texture.
     size: (u32, u32),
+    /// Format of the bytes. Decoded images are always Rgba8UnormSrgb.
+    format: wgpu::TextureFormat,
+}
+
+impl Default for Texture {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            bytes: Vec::new(),
+            size: (0, 0),
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+        }
+    }
 }
 
 impl Texture {
@@ -21,6 +37,8 @@ impl Texture {
     }
 
     /// Creates a [`Texture`] from loaded File.
+    /// Files ending in `.ktx2` or `.dds` keep their GPU format, see
+    /// [`Texture::from_ktx2`] and [`Texture::from_dds`].
     ///
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self, GraphicsError> {
         let name = path
@@ -28,8 +46,17 @@ impl Texture {
             .to_str()
             .ok_or_else(|| Error::other("could not convert name to String"))?
             .to_owned();
+        let extension = path
+            .as_ref()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
 
-        Ok(Self::from_image(name, image::open(path)?))
+        match extension.as_deref() {
+            Some("ktx2") => Self::from_ktx2(name, &fs::read(path)?),
+            Some("dds") => Self::from_dds(name, &fs::read(path)?),
+            _ => Ok(Self::from_image(name, image::open(path)?)),
+        }
     }
 
     /// Creates a [`Texture`] from loaded File and uploads it to an [`AtlasSet`].
@@ -47,6 +74,11 @@ impl Texture {
         } else {
             let texture = Texture::from_file(path).ok()?;
             let (width, height) = texture.size();
+
+            if !texture.fits_format(atlas.format) {
+                return None;
+            }
+
             atlas.upload(name, texture.bytes(), width, height, 0, renderer)
         }
     }
@@ -66,6 +98,11 @@ impl Texture {
         } else {
             let texture = Texture::from_file(path).ok()?;
             let (width, height) = texture.size();
+
+            if !texture.fits_format(atlas.format) {
+                return None;
+            }
+
             atlas.upload_with_alloc(
                 name,
                 texture.bytes(),
@@ -83,16 +120,77 @@ impl Texture {
         let size = image.dimensions();
         let bytes = image.into_rgba8().into_raw();
 
-        Self { name, bytes, size }
+        Self {
+            name,
+            bytes,
+            size,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+        }
+    }
+
+    /// Creates a [`Texture`] from bytes already in a GPU format such as a
+    /// block compressed one. Only the first image is kept when there are
+    /// more bytes, like further mip levels.
+    ///
+    pub fn from_raw(
+        name: String,
+        mut bytes: Vec<u8>,
+        size: (u32, u32),
+        format: wgpu::TextureFormat,
+    ) -> Result<Self, GraphicsError> {
+        let needed = image_byte_size(format, size.0, size.1)
+            .ok_or(GraphicsError::UnsupportedFormat(format))?;
+
+        if size.0 == 0 || size.1 == 0 || bytes.len() < needed {
+            return Err(GraphicsError::UnsupportedTextureFile(
+                name,
+                format!("expected {needed} bytes but got {}", bytes.len()),
+            ));
+        }
+
+        bytes.truncate(needed);
+
+        Ok(Self {
+            name,
+            bytes,
+            size,
+            format,
+        })
+    }
+
+    /// Creates a [`Texture`] from a KTX2 file's bytes keeping its GPU
+    /// format. Only the first mip level of the first layer is loaded.
+    /// Supercompressed (Basis Universal or Zstandard) files are not supported.
+    ///
+    pub fn from_ktx2(name: String, data: &[u8]) -> Result<Self, GraphicsError> {
+        let (bytes, size, format) = decode_ktx2(&name, data)?;
+
+        Self::from_raw(name, bytes, size, format)
+    }
+
+    /// Creates a [`Texture`] from a DDS file's bytes keeping its GPU format.
+    /// Only the first mip level of the first layer is loaded.
+    ///
+    pub fn from_dds(name: String, data: &[u8]) -> Result<Self, GraphicsError> {
+        let (bytes, size, format) = decode_dds(&name, data)?;
+
+        Self::from_raw(name, bytes, size, format)
     }
 
     /// Creates a [`Texture`] from Memory.
+    /// KTX2 and DDS data is detected and keeps its GPU format.
     ///
     pub fn from_memory(
         name: String,
         data: &[u8],
     ) -> Result<Self, GraphicsError> {
-        Ok(Self::from_image(name, image::load_from_memory(data)?))
+        if is_ktx2(data) {
+            Self::from_ktx2(name, data)
+        } else if is_dds(data) {
+            Self::from_dds(name, data)
+        } else {
+            Ok(Self::from_image(name, image::load_from_memory(data)?))
+        }
     }
 
     /// Creates a [`Texture`] from Memory with [`ImageFormat`].
@@ -116,6 +214,10 @@ impl Texture {
         atlas: &mut AtlasSet<String, i32>,
         renderer: &GpuRenderer,
     ) -> Option<usize> {
+        if !self.fits_format(atlas.format) {
+            return None;
+        }
+
         let (width, height) = self.size;
         atlas.upload(self.name.clone(), &self.bytes, width, height, 0, renderer)
     }
@@ -128,6 +230,10 @@ impl Texture {
         atlas: &mut AtlasSet<String, i32>,
         renderer: &GpuRenderer,
     ) -> Option<(usize, Allocation)> {
+        if !self.fits_format(atlas.format) {
+            return None;
+        }
+
         let (width, height) = self.size;
         atlas.upload_with_alloc(
             self.name.clone(),
@@ -141,6 +247,7 @@ impl Texture {
 
     /// Splits the Texture into Tiles.
     /// Returns a Optional new [`TileSheet`] upon completion.
+    /// Compressed Textures can not be split and return None.
     ///
     pub fn new_tilesheet(
         self,
@@ -175,4 +282,23 @@ impl Texture {
     pub fn size(&self) -> (u32, u32) {
         self.size
     }
+
+    /// Returns the [`wgpu::TextureFormat`] the bytes are in.
+    ///
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    /// If the bytes are in a block compressed format.
+    ///
+    pub fn is_compressed(&self) -> bool {
+        self.format.is_compressed()
+    }
+
+    /// If the bytes can be uploaded into an [`AtlasSet`] of format.
+    /// Only the color space is allowed to differ.
+    ///
+    pub fn fits_format(&self, format: wgpu::TextureFormat) -> bool {
+        self.format.remove_srgb_suffix() == format.remove_srgb_suffix()
+    }
 }
@@ -1,6 +1,6 @@
 use wgpu::BindGroupLayout;
 
-use crate::GpuRenderer;
+use crate::{GpuRenderer, SamplerSettings};
 
 /// [`crate::AtlasSet`] rendering TextureGroup
 ///
@@ -11,18 +11,40 @@ pub struct TextureGroup {
     pub bind_group: wgpu::BindGroup,
 }
 
+/// Trait used to get the [`TextureGroup`] a renderer will draw Textures from.
+/// Implemented by [`crate::AtlasSet`] and [`crate::RenderTarget`].
+///
+pub trait AsTextureGroup {
+    /// Returns a reference to the [`TextureGroup`].
+    ///
+    fn texture_group(&self) -> &TextureGroup;
+}
+
 impl TextureGroup {
+    /// Creates a [`TextureGroup`] using the default [`SamplerSettings`].
+    ///
     pub fn from_view(
         renderer: &GpuRenderer,
         texture_view: wgpu::TextureView,
         layout: &BindGroupLayout,
     ) -> Self {
-        let diffuse_sampler =
-            renderer.device().create_sampler(&wgpu::SamplerDescriptor {
-                label: Some("Texture_sampler"),
-                lod_max_clamp: 0.0,
-                ..Default::default()
-            });
+        Self::from_view_with_sampler(
+            renderer,
+            texture_view,
+            layout,
+            &SamplerSettings::default(),
+        )
+    }
+
+    /// Creates a [`TextureGroup`] whose sampler uses the [`SamplerSettings`].
+    ///
+    pub fn from_view_with_sampler(
+        renderer: &GpuRenderer,
+        texture_view: wgpu::TextureView,
+        layout: &BindGroupLayout,
+        sampler: &SamplerSettings,
+    ) -> Self {
+        let diffuse_sampler = sampler.create_sampler(renderer.device());
 
         Self {
             bind_group: renderer.device().create_bind_group(
@@ -0,0 +1,249 @@
+use crate::{AHashMap, Allocation, AtlasSet, GpuRenderer, Image, Texture};
+use log::{error, warn};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+/// A Texture that was reloaded by a [`TextureWatcher`].
+///
+#[derive(Copy, Clone, Debug)]
+pub struct TextureReload {
+    /// [`AtlasSet`] Index of the Texture. This does not change on reload.
+    pub index: usize,
+    /// [`Allocation`] now holding the Texture.
+    pub allocation: Allocation,
+    /// If the size changed and a new [`Allocation`] was made.
+    pub reallocated: bool,
+}
+
+/// File being watched and when it was last modified.
+///
+struct WatchedFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+/// Development helper that reloads Textures into an [`AtlasSet`] when their
+/// files change on disk so art can be iterated on while running.
+///
+/// Files are polled for their modified time, at most once per interval, so
+/// no platform watcher is needed. Textures keep their [`AtlasSet`] Index.
+/// Those that changed size get a new [`Allocation`] and must be rebuilt.
+///
+/// # Suggested usage
+/// Call [`TextureWatcher::watch_atlas`] after loading with
+/// [`Texture::upload_from`] or a [`crate::TextureLoader`], then call
+/// [`TextureWatcher::poll`] once per frame and pass the result to
+/// [`TextureWatcher::mark_images`].
+///
+pub struct TextureWatcher {
+    /// Watched files by the key they are stored under in the [`AtlasSet`].
+    files: AHashMap<String, WatchedFile>,
+    /// Minimum time between checking the files.
+    interval: Duration,
+    /// When the files were last checked.
+    last_poll: Option<Instant>,
+}
+
+impl Default for TextureWatcher {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(500))
+    }
+}
+
+impl TextureWatcher {
+    /// Creates a new [`TextureWatcher`] checking files at most once per
+    /// interval.
+    ///
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            files: AHashMap::default(),
+            interval,
+            last_poll: None,
+        }
+    }
+
+    /// Sets the minimum time between checking the files.
+    ///
+    pub fn set_interval(&mut self, interval: Duration) -> &mut Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Starts watching a file. The path is used as key within the
+    /// [`AtlasSet`] like [`Texture::upload_from`].
+    ///
+    pub fn watch(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        let path = path.as_ref();
+
+        if let Some(key) = path.to_str() {
+            self.files.insert(
+                key.to_owned(),
+                WatchedFile {
+                    path: path.to_path_buf(),
+                    modified: modified(path),
+                },
+            );
+        }
+
+        self
+    }
+
+    /// Watches every key within the [`AtlasSet`] that is the path of an
+    /// existing file, which covers everything loaded with
+    /// [`Texture::upload_from`]. Already watched files are kept as they are.
+    ///
+    pub fn watch_atlas(&mut self, atlas: &AtlasSet<String, i32>) -> &mut Self {
+        for key in atlas.lookup.keys() {
+            if self.files.contains_key(key) || !Path::new(key).is_file() {
+                continue;
+            }
+
+            self.watch(key);
+        }
+
+        self
+    }
+
+    /// Stops watching a file.
+    ///
+    pub fn unwatch(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        if let Some(key) = path.as_ref().to_str() {
+            self.files.remove(key);
+        }
+
+        self
+    }
+
+    /// If the file is being watched.
+    ///
+    pub fn is_watching(&self, path: impl AsRef<Path>) -> bool {
+        path.as_ref()
+            .to_str()
+            .is_some_and(|key| self.files.contains_key(key))
+    }
+
+    /// Returns the amount of watched files.
+    ///
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// If no files are watched.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Reloads changed files once the interval has passed since the last
+    /// check. See [`TextureWatcher::reload_changed`].
+    ///
+    pub fn poll(
+        &mut self,
+        atlas: &mut AtlasSet<String, i32>,
+        renderer: &GpuRenderer,
+    ) -> Vec<TextureReload> {
+        let now = Instant::now();
+
+        if self
+            .last_poll
+            .is_some_and(|last| now.duration_since(last) < self.interval)
+        {
+            return Vec::new();
+        }
+
+        self.last_poll = Some(now);
+        self.reload_changed(atlas, renderer)
+    }
+
+    /// Checks every watched file right away and re-uploads those whose
+    /// modified time changed. Files that fail to decode, for example while
+    /// still being written, are logged and retried on their next change.
+    /// Files no longer within the [`AtlasSet`] stop being watched.
+    ///
+    /// Returns the reloaded Textures.
+    ///
+    pub fn reload_changed(
+        &mut self,
+        atlas: &mut AtlasSet<String, i32>,
+        renderer: &GpuRenderer,
+    ) -> Vec<TextureReload> {
+        let mut reloads = Vec::new();
+
+        self.files.retain(|key, _| atlas.lookup.contains_key(key));
+
+        for (key, file) in self.files.iter_mut() {
+            let current = modified(&file.path);
+
+            if current.is_none() || current == file.modified {
+                continue;
+            }
+
+            file.modified = current;
+
+            let texture = match Texture::from_file(&file.path) {
+                Ok(texture) => texture,
+                Err(e) => {
+                    error!("Texture {key} failed to reload: {e}");
+                    continue;
+                }
+            };
+
+            if !texture.fits_format(atlas.format) {
+                error!(
+                    "Texture {key} is {:?} but the AtlasSet is {:?}.",
+                    texture.format(),
+                    atlas.format
+                );
+                continue;
+            }
+
+            let (width, height) = texture.size();
+
+            match atlas.reupload(key, texture.bytes(), width, height, renderer)
+            {
+                Ok(Some((index, allocation, reallocated))) => {
+                    reloads.push(TextureReload {
+                        index,
+                        allocation,
+                        reallocated,
+                    })
+                }
+                Ok(None) => {
+                    warn!("Texture {key} could not be placed in the AtlasSet.")
+                }
+                Err(e) => error!("Texture {key} failed to upload: {e}"),
+            }
+        }
+
+        reloads
+    }
+
+    /// Marks every [`Image`] using a reloaded Texture as changed so its
+    /// buffers get rebuilt on the next update.
+    ///
+    pub fn mark_images<'a>(
+        reloads: &[TextureReload],
+        images: impl IntoIterator<Item = &'a mut Image>,
+    ) {
+        if reloads.is_empty() {
+            return;
+        }
+
+        for image in images {
+            if image.texture.is_some_and(|texture| {
+                reloads.iter().any(|reload| reload.index == texture)
+            }) {
+                image.changed = true;
+            }
+        }
+    }
+}
+
+/// Returns when the file was last modified if it can be read.
+///
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
@@ -27,6 +27,7 @@ impl TileSheet {
     /// This splits the [`Texture`] into [`Tile`]'s using tilesize and places them into the [`AtlasSet`].
     ///
     /// Returns a Optional [`TileSheet`] upon successful completion.
+    /// Compressed Textures can not be split and return None.
     ///
     pub fn new(
         texture: Texture,
@@ -34,6 +35,10 @@ impl TileSheet {
         atlas: &mut AtlasSet<String, i32>,
         tilesize: u32,
     ) -> Option<TileSheet> {
+        if texture.is_compressed() {
+            return None;
+        }
+
         let tilecount =
             (texture.size().0 / tilesize) * (texture.size().1 / tilesize);
         let sheet_width = texture.size().0 / tilesize;
@@ -114,6 +119,7 @@ impl TileSheet {
     /// This splits the [`Texture`] into [`Tile`]'s using tilesize and places them into the [`AtlasSet`].
     ///
     /// Returns a Some(()) upon successful completion.
+    /// Compressed Textures can not be split and return None.
     ///
     pub fn upload(
         &mut self,
@@ -122,6 +128,10 @@ impl TileSheet {
         atlas: &mut AtlasSet<String, i32>,
         tilesize: u32,
     ) -> Option<()> {
+        if texture.is_compressed() {
+            return None;
+        }
+
         let tilecount =
             (texture.size().0 / tilesize) * (texture.size().1 / tilesize);
         let sheet_width = texture.size().0 / tilesize;
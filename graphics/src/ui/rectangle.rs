@@ -1,6 +1,7 @@
 use crate::{
     AtlasSet, Bounds, CameraType, DrawOrder, GpuRenderer, GraphicsError, Index,
-    OrderedIndex, OtherError, RectVertex, Texture, Vec2, Vec3, Vec4,
+    OrderedIndex, OtherError, RectVertex, RenderTarget, Texture, Vec2, Vec3,
+    Vec4,
 };
 use cosmic_text::Color;
 
@@ -34,6 +35,8 @@ pub struct Rect {
     pub bounds: Option<Bounds>,
     /// If anything got updated we need to update the buffers too.
     pub changed: bool,
+    /// Size of the [`RenderTarget`] the Buffers were last built from.
+    pub target_size: (u32, u32),
 }
 
 impl Rect {
@@ -57,6 +60,7 @@ impl Rect {
             order: DrawOrder::new(false, Vec3::default(), order_layer),
             bounds: None,
             changed: true,
+            target_size: (0, 0),
         }
     }
 
@@ -215,6 +219,36 @@ impl Rect {
             ([0.0, 0.0, 0.0, 0.0], 0)
         };
 
+        self.write_quad(renderer, uv, layer);
+    }
+
+    /// Updates the [`Rect`]'s Buffers to render using a [`RenderTarget`]
+    /// as its Texture.
+    ///
+    fn create_target_quad(
+        &mut self,
+        renderer: &mut GpuRenderer,
+        target: &RenderTarget,
+    ) {
+        let (width, height) = target.size();
+        let uv = [
+            self.uv.x,
+            self.uv.y,
+            self.uv.z.min(width as f32),
+            self.uv.w.min(height as f32),
+        ];
+
+        self.write_quad(renderer, uv, 0);
+    }
+
+    /// Writes the [`Rect`]'s Instance into its Buffer Store.
+    ///
+    fn write_quad(
+        &mut self,
+        renderer: &mut GpuRenderer,
+        uv: [f32; 4],
+        layer: u32,
+    ) {
         let instance = RectVertex {
             position: self.position.to_array(),
             size: self.size.to_array(),
@@ -262,6 +296,36 @@ impl Rect {
         )
     }
 
+    /// Used to check and update the vertex array using a [`RenderTarget`]
+    /// as the Texture. Set the uv with [`Rect::set_container_uv`] to the
+    /// area of the Target to show.
+    /// Returns a [`OrderedIndex`] used in Rendering.
+    ///
+    pub fn update_with_target(
+        &mut self,
+        renderer: &mut GpuRenderer,
+        target: &RenderTarget,
+    ) -> OrderedIndex {
+        // The uv clamps depend on the Targets size.
+        if self.target_size != target.size() {
+            self.target_size = target.size();
+            self.changed = true;
+        }
+
+        if self.changed {
+            self.create_target_quad(renderer, target);
+            self.changed = false;
+        }
+
+        OrderedIndex::new_with_bounds(
+            self.order,
+            self.store_id,
+            0,
+            self.bounds,
+            self.camera_type,
+        )
+    }
+
     /// Checks if the Mouse position is within the Rects location.
     ///
     pub fn check_mouse_bounds(&self, mouse_pos: Vec2) -> bool {
@@ -1,6 +1,7 @@
 use crate::{
-    AtlasSet, GpuRenderer, GraphicsError, InstanceBuffer, OrderedIndex, Rect,
-    RectRenderPipeline, RectVertex, StaticVertexBuffer, System,
+    AsTextureGroup, AtlasSet, BlendMode, GpuRenderer, GraphicsError,
+    InstanceBuffer, OrderedIndex, Rect, RectRenderPipeline, RectVertex,
+    RenderTarget, StaticVertexBuffer, System,
 };
 
 /// Instance Buffer Setup for [`Rect`]'s.
@@ -8,6 +9,8 @@ use crate::{
 pub struct RectRenderer {
     /// Instance Buffer holding all Rendering information for [`Rect`]'s.
     pub buffer: InstanceBuffer<RectVertex>,
+    /// [`BlendMode`] the Renderer draws with.
+    blend_mode: BlendMode,
 }
 
 impl RectRenderer {
@@ -16,9 +19,34 @@ impl RectRenderer {
     pub fn new(renderer: &GpuRenderer) -> Result<Self, GraphicsError> {
         Ok(Self {
             buffer: InstanceBuffer::new(renderer.gpu_device(), 512),
+            blend_mode: BlendMode::Alpha,
         })
     }
 
+    /// Creates a new [`RectRenderer`] that draws using a [`BlendMode`].
+    /// Creates the [`RectRenderPipeline`] for the mode if it does not yet exist.
+    ///
+    /// # Arguments
+    /// - blend_mode: [`BlendMode`] used to draw with.
+    ///
+    pub fn with_blend_mode(
+        renderer: &mut GpuRenderer,
+        blend_mode: BlendMode,
+    ) -> Result<Self, GraphicsError> {
+        renderer.create_pipeline(RectRenderPipeline::new(blend_mode));
+
+        let mut this = Self::new(renderer)?;
+
+        this.blend_mode = blend_mode;
+        Ok(this)
+    }
+
+    /// Returns the [`BlendMode`] the Renderer draws with.
+    ///
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
     /// Adds a Buffer [`OrderedIndex`] to the Rendering Store to get processed.
     /// This must be done before [`RectRenderer::finalize`] but after [`Rect::update`] in order for it to Render.
     ///
@@ -62,6 +90,28 @@ impl RectRenderer {
         self.add_buffer_store(renderer, index, buffer_layer);
     }
 
+    /// Updates a [`Rect`] that draws a [`RenderTarget`] and adds its [`OrderedIndex`]
+    /// to staging using [`RectRenderer::add_buffer_store`].
+    /// The [`RectRenderer`] must then be rendered using the [`RenderTarget`]
+    /// so use a separate [`RectRenderer`] from the ones using an [`AtlasSet`].
+    ///
+    /// # Arguments
+    /// - rect: [`Rect`] we want to update and prepare for rendering.
+    /// - target: [`RenderTarget`] the [`Rect`] needs to render with.
+    /// - buffer_layer: The Buffer Layer we want to add this Object too.
+    ///
+    pub fn rect_target_update(
+        &mut self,
+        rect: &mut Rect,
+        renderer: &mut GpuRenderer,
+        target: &RenderTarget,
+        buffer_layer: usize,
+    ) {
+        let index = rect.update_with_target(renderer, target);
+
+        self.add_buffer_store(renderer, index, buffer_layer);
+    }
+
     /// Sets the Instance Buffer to enable Rendering With Scissor Clipping.
     /// This must be Set for the Optional Bounds to be used.
     ///
@@ -77,12 +127,13 @@ where
     Controls: camera::controls::Controls,
 {
     /// Renders the all [`Rect`]'s within the buffer layer to screen that have been processed and finalized.
+    /// atlas can be an [`AtlasSet`] or a [`RenderTarget`].
     ///
-    fn render_rects(
+    fn render_rects<T: AsTextureGroup>(
         &mut self,
         renderer: &'b GpuRenderer,
         buffer: &'b RectRenderer,
-        atlas: &'b AtlasSet,
+        atlas: &'b T,
         system: &'b System<Controls>,
         buffer_layer: usize,
     );
@@ -93,11 +144,11 @@ where
     'b: 'a,
     Controls: camera::controls::Controls,
 {
-    fn render_rects(
+    fn render_rects<T: AsTextureGroup>(
         &mut self,
         renderer: &'b GpuRenderer,
         buffer: &'b RectRenderer,
-        atlas: &'b AtlasSet,
+        atlas: &'b T,
         system: &'b System<Controls>,
         buffer_layer: usize,
     ) {
@@ -110,12 +161,16 @@ where
                 if buffer.buffer.count() > 0 {
                     self.set_bind_group(
                         1,
-                        &atlas.texture_group.bind_group,
+                        &atlas.texture_group().bind_group,
                         &[],
                     );
                     self.set_vertex_buffer(1, buffer.buffer.instances(None));
                     self.set_pipeline(
-                        renderer.get_pipelines(RectRenderPipeline).unwrap(),
+                        renderer
+                            .get_pipelines(RectRenderPipeline::new(
+                                buffer.blend_mode,
+                            ))
+                            .unwrap(),
                     );
 
                     for (details, bounds, camera_type) in details {
@@ -154,10 +209,14 @@ where
             buffer.buffer.buffers.get(buffer_layer)
         {
             if buffer.buffer.count() > 0 {
-                self.set_bind_group(1, &atlas.texture_group.bind_group, &[]);
+                self.set_bind_group(1, &atlas.texture_group().bind_group, &[]);
                 self.set_vertex_buffer(1, buffer.buffer.instances(None));
                 self.set_pipeline(
-                    renderer.get_pipelines(RectRenderPipeline).unwrap(),
+                    renderer
+                        .get_pipelines(RectRenderPipeline::new(
+                            buffer.blend_mode,
+                        ))
+                        .unwrap(),
                 );
 
                 self.draw_indexed(